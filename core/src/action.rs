@@ -42,6 +42,22 @@ impl ActionWrapper {
     pub fn id(&self) -> &snowflake::ProcessUniqueId {
         &self.id
     }
+
+    /// name of the Action variant, e.g. for logging and metrics
+    pub fn action_name(&self) -> &'static str {
+        match self.action {
+            Action::Commit(_) => "Commit",
+            Action::GetEntry(_) => "GetEntry",
+            Action::GetLinks { .. } => "GetLinks",
+            Action::ExecuteZomeFunction(_) => "ExecuteZomeFunction",
+            Action::ReturnZomeFunctionResult(_) => "ReturnZomeFunctionResult",
+            Action::CancelZomeFunction(_) => "CancelZomeFunction",
+            Action::InitApplication(_) => "InitApplication",
+            Action::ReturnInitializationResult(_) => "ReturnInitializationResult",
+            Action::ValidateEntry(_) => "ValidateEntry",
+            Action::Heartbeat => "Heartbeat",
+        }
+    }
 }
 
 impl PartialEq for ActionWrapper {
@@ -68,11 +84,19 @@ pub enum Action {
     Commit(Entry),
     /// GetEntry by hash
     GetEntry(HashString),
+    /// GetLinks of a given entry type from the base entry hash
+    GetLinks {
+        base: HashString,
+        entry_type: String,
+    },
 
     /// execute a function in a zome WASM
     ExecuteZomeFunction(ZomeFnCall),
     /// return the result of a zome WASM function call
     ReturnZomeFunctionResult(ZomeFnResult),
+    /// abort a zome WASM function call that is still running
+    /// the call returns a "cancelled" error as its result
+    CancelZomeFunction(ZomeFnCall),
 
     /// initialize an application from a Dna
     /// not the same as genesis
@@ -85,12 +109,23 @@ pub enum Action {
     /// ???
     // @TODO how does this relate to validating a commit?
     ValidateEntry(EntrySubmission),
+
+    /// changes nothing but still runs every observer against the current state
+    /// lets consumers re-poll observers waiting on something outside of the state
+    Heartbeat,
 }
 
 /// function signature for action handler functions
 // @TODO merge these into a single signature
 // @see https://github.com/holochain/holochain-rust/issues/194
-pub type AgentReduceFn = ReduceFn<AgentState>;
+/// agent handlers return follow-up actions to dispatch once the state is reduced
+pub type AgentReduceFn = fn(
+    Arc<Context>,
+    &mut AgentState,
+    &ActionWrapper,
+    &Sender<ActionWrapper>,
+    &Sender<Observer>,
+) -> Vec<ActionWrapper>;
 pub type NucleusReduceFn = ReduceFn<NucleusState>;
 pub type ReduceFn<S> =
     fn(Arc<Context>, &mut S, &ActionWrapper, &Sender<ActionWrapper>, &Sender<Observer>);
@@ -101,7 +136,11 @@ pub mod tests {
     use action::{Action, ActionWrapper};
     use hash::tests::test_hash;
     use hash_table::entry::tests::{test_entry, test_entry_hash};
-    use nucleus::tests::test_call_result;
+    use holochain_dna::Dna;
+    use nucleus::{
+        tests::{test_call_result, test_zome_call},
+        EntrySubmission,
+    };
     use test_utils::calculate_hash;
 
     /// dummy action
@@ -170,6 +209,46 @@ pub mod tests {
         assert_ne!(aw1.id(), aw2.id());
     }
 
+    #[test]
+    /// tests the name of every action variant
+    fn action_wrapper_action_name() {
+        for (action, name) in vec![
+            (Action::Commit(test_entry()), "Commit"),
+            (Action::GetEntry(test_hash()), "GetEntry"),
+            (
+                Action::GetLinks {
+                    base: test_hash(),
+                    entry_type: "testEntryType".to_string(),
+                },
+                "GetLinks",
+            ),
+            (
+                Action::ExecuteZomeFunction(test_zome_call()),
+                "ExecuteZomeFunction",
+            ),
+            (
+                Action::ReturnZomeFunctionResult(test_call_result()),
+                "ReturnZomeFunctionResult",
+            ),
+            (
+                Action::CancelZomeFunction(test_zome_call()),
+                "CancelZomeFunction",
+            ),
+            (Action::InitApplication(Dna::new()), "InitApplication"),
+            (
+                Action::ReturnInitializationResult(None),
+                "ReturnInitializationResult",
+            ),
+            (
+                Action::ValidateEntry(EntrySubmission::new("zome", "type", "content")),
+                "ValidateEntry",
+            ),
+            (Action::Heartbeat, "Heartbeat"),
+        ] {
+            assert_eq!(name, ActionWrapper::new(action).action_name());
+        }
+    }
+
     #[test]
     /// tests that action wrapper hashes are unique
     fn action_wrapper_hash() {
@@ -1,10 +1,12 @@
 use agent::keys::Keys;
+use chain::header::Header;
 use error::HolochainError;
 use futures::executor::block_on;
-use hash_table::{pair::Pair, pair_meta::PairMeta};
+use hash_table::{pair::Pair, pair_meta::PairMeta, TableStats};
 use riker::actors::*;
 use riker_default::DefaultModel;
 use riker_patterns::ask::ask;
+use std::sync::mpsc::Sender;
 
 #[derive(Clone, Debug)]
 /// riker protocol for all our actors
@@ -19,6 +21,25 @@ pub enum Protocol {
     GetTopPair,
     GetTopPairResult(Option<Pair>),
 
+    /// Chain::genesis_hash()
+    GetGenesisHash,
+    GetGenesisHashResult(Option<String>),
+
+    /// Chain::clear()
+    ClearTopPair,
+    ClearTopPairResult(Result<(), HolochainError>),
+
+    /// Chain::reset_top_pair()
+    ResetTopPair {
+        top_pair: Option<Pair>,
+        genesis_hash: Option<String>,
+    },
+    ResetTopPairResult(Result<(), HolochainError>),
+
+    /// Chain::subscribe_commits()
+    SubscribeCommits(Sender<Pair>),
+    SubscribeCommitsResult,
+
     /// HashTable::setup()
     Setup,
     SetupResult(Result<(), HolochainError>),
@@ -58,9 +79,25 @@ pub enum Protocol {
     GetPair(String),
     GetPairResult(Result<Option<Pair>, HolochainError>),
 
+    /// HashTable::pairs()
+    GetPairBatch(Vec<String>),
+    GetPairBatchResult(Result<Vec<Option<Pair>>, HolochainError>),
+
+    /// HashTable::header()
+    GetHeader(String),
+    GetHeaderResult(Result<Option<Header>, HolochainError>),
+
     /// HashTable::put_pair()
     PutPair(Pair),
     PutPairResult(Result<(), HolochainError>),
+
+    /// HashTable::put_pairs()
+    PutPairBatch(Vec<Pair>),
+    PutPairBatchResult(Result<(), HolochainError>),
+
+    /// HashTable::stats()
+    Stats,
+    StatsResult(Result<TableStats, HolochainError>),
 }
 
 /// this is the global state that manages every actor
@@ -1,4 +1,4 @@
-#[derive(Clone, Debug, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 /// represents a single Key
 /// e.g. private + public keys would be two Key structs
 pub struct Key {}
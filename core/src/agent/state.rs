@@ -1,14 +1,19 @@
 use action::{Action, ActionWrapper, AgentReduceFn};
-use agent::keys::Keys;
+use actor::Protocol;
+use agent::keys::{Key, Keys};
 use chain::{Chain, SourceChain};
+use chrono::{DateTime, Utc};
 use context::Context;
 use error::HolochainError;
-use hash_table::pair::Pair;
+use hash_table::{entry::Entry, pair::Pair, sys_entry::EntryType, HashTable};
 use instance::Observer;
 use json::ToJson;
-use key::Key;
+use riker::actors::*;
+use serde_json;
 use std::{
-    collections::HashMap,
+    any::Any,
+    collections::{HashMap, VecDeque},
+    panic::{self, AssertUnwindSafe},
     sync::{mpsc::Sender, Arc},
 };
 
@@ -19,8 +24,10 @@ pub struct AgentState {
     /// every action and the result of that action
     // @TODO this will blow up memory, implement as some kind of dropping/FIFO with a limit?
     // @see https://github.com/holochain/holochain-rust/issues/166
-    actions: HashMap<ActionWrapper, ActionResponse>,
+    actions: HashMap<ActionWrapper, TimestampedResponse>,
     chain: Chain,
+    /// public entries committed but not yet published to the DHT, oldest first
+    publish_queue: VecDeque<Entry>,
 }
 
 impl AgentState {
@@ -30,6 +37,7 @@ impl AgentState {
             keys: None,
             actions: HashMap::new(),
             chain: chain.clone(),
+            publish_queue: VecDeque::new(),
         }
     }
 
@@ -43,11 +51,124 @@ impl AgentState {
         &self.chain
     }
 
+    /// getter for the public entries waiting to be published to the DHT, oldest first
+    pub fn pending_publishes(&self) -> &VecDeque<Entry> {
+        &self.publish_queue
+    }
+
     /// getter for a copy of self.actions
     /// uniquely maps action executions to the result of the action
     pub fn actions(&self) -> HashMap<ActionWrapper, ActionResponse> {
+        self.actions
+            .iter()
+            .map(|(action_wrapper, timestamped)| {
+                (action_wrapper.clone(), timestamped.response.clone())
+            })
+            .collect()
+    }
+
+    /// getter for a copy of self.actions including when each response was produced
+    pub fn actions_with_time(&self) -> HashMap<ActionWrapper, TimestampedResponse> {
         self.actions.clone()
     }
+
+    /// number of action responses currently stored
+    pub fn action_count(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// ratio of successful commits to all commits in the stored action responses
+    /// None if there are no commits
+    pub fn commit_success_rate(&self) -> Option<f64> {
+        let mut ok: u32 = 0;
+        let mut total: u32 = 0;
+        for timestamped in self.actions.values() {
+            if let ActionResponse::Commit(ref result) = timestamped.response {
+                total += 1;
+                if result.is_ok() {
+                    ok += 1;
+                }
+            }
+        }
+        if total == 0 {
+            None
+        } else {
+            Some(f64::from(ok) / f64::from(total))
+        }
+    }
+
+    /// drops every action response stored before the cutoff
+    pub fn prune_actions_older_than(&mut self, cutoff: DateTime<Utc>) {
+        self.actions
+            .retain(|_, timestamped| timestamped.at >= cutoff);
+    }
+
+    /// swaps in a new chain and drops the action history, e.g. when adopting a new DNA
+    /// unlike a new AgentState the keys and so the agent's identity are kept
+    pub fn reset_chain(&mut self, chain: &Chain) {
+        self.chain = chain.clone();
+        self.actions.clear();
+    }
+
+    /// stores the response to an action as of now
+    fn insert_action(&mut self, action_wrapper: &ActionWrapper, response: ActionResponse) {
+        self.insert_action_at(action_wrapper, response, Utc::now());
+    }
+
+    /// stores the response to an action as of the given time
+    fn insert_action_at(
+        &mut self,
+        action_wrapper: &ActionWrapper,
+        response: ActionResponse,
+        time: DateTime<Utc>,
+    ) {
+        self.actions.insert(
+            action_wrapper.clone(),
+            TimestampedResponse { response, at: time },
+        );
+    }
+
+    /// captures the chain and public key for restoring this state later, e.g. after a crash
+    /// the action history and the private key are not included
+    pub fn to_snapshot(&self) -> Result<AgentSnapshot, HolochainError> {
+        Ok(AgentSnapshot {
+            chain: self.chain.to_json()?,
+            public_key: self.keys.as_ref().map(|keys| keys.public_key()),
+        })
+    }
+
+    /// rebuilds an AgentState from a snapshot with its chain over the given table
+    /// the restored state has no keys or action history
+    pub fn from_snapshot(
+        table: ActorRef<Protocol>,
+        snapshot: &AgentSnapshot,
+    ) -> Result<AgentState, HolochainError> {
+        let mut pairs: Vec<Pair> = serde_json::from_str(&snapshot.chain)?;
+        pairs.reverse();
+
+        let mut chain = Chain::new(table);
+        for p in pairs {
+            chain.push_pair(&p)?;
+        }
+        Ok(AgentState::new(&chain))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// serializable checkpoint of an AgentState
+/// @see AgentState::to_snapshot()
+pub struct AgentSnapshot {
+    /// the chain as JSON, top to bottom
+    pub chain: String,
+    /// public key of the agent, None if the agent had no keys
+    pub public_key: Option<Key>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// an ActionResponse with the time it was produced
+pub struct TimestampedResponse {
+    pub response: ActionResponse,
+    pub at: DateTime<Utc>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -58,20 +179,29 @@ impl AgentState {
 // @see https://github.com/holochain/holochain-rust/issues/196
 pub enum ActionResponse {
     Commit(Result<Pair, HolochainError>),
-    GetEntry(Option<Pair>),
+    GetEntry(Result<Option<Pair>, HolochainError>),
+    GetLinks(Result<Vec<String>, HolochainError>),
+    /// the reducer for the action panicked so the action had no effect
+    Failed(HolochainError),
 }
 
 impl ToJson for ActionResponse {
     fn to_json(&self) -> Result<String, HolochainError> {
         match self {
             ActionResponse::Commit(result) => match result {
-                Ok(pair) => Ok(format!("{{\"hash\":\"{}\"}}", pair.entry().key())),
+                Ok(pair) => Ok(format!("{{\"hash\":\"{}\"}}", pair.entry_address())),
                 Err(err) => Ok((*err).to_json()?),
             },
             ActionResponse::GetEntry(result) => match result {
-                Some(pair) => Ok(pair.to_json()?),
-                None => Ok("".to_string()),
+                Ok(Some(pair)) => Ok(pair.to_json()?),
+                Ok(None) => Ok("".to_string()),
+                Err(err) => Ok((*err).to_json()?),
             },
+            ActionResponse::GetLinks(result) => match result {
+                Ok(hashes) => Ok(serde_json::to_string(hashes)?),
+                Err(err) => Ok((*err).to_json()?),
+            },
+            ActionResponse::Failed(err) => Ok(err.to_json()?),
         }
     }
 }
@@ -88,44 +218,109 @@ fn reduce_commit(
     action_wrapper: &ActionWrapper,
     _action_channel: &Sender<ActionWrapper>,
     _observer_channel: &Sender<Observer>,
-) {
+) -> Vec<ActionWrapper> {
     let action = action_wrapper.action();
     let entry = unwrap_to!(action => Action::Commit);
 
     // @TODO validation dispatch should go here rather than upstream in invoke_commit
     // @see https://github.com/holochain/holochain-rust/issues/256
 
-    state.actions.insert(
-        action_wrapper.clone(),
-        ActionResponse::Commit(state.chain.push_entry(&entry)),
-    );
+    // a chain has a single genesis so a DNA entry at the genesis can't be committed again
+    let dna_type = EntryType::Dna.as_str();
+    let genesis = match state.chain.genesis_hash() {
+        Some(ref genesis_hash) if entry.entry_type() == dna_type => state.chain.pair(genesis_hash),
+        _ => Ok(None),
+    };
+    let response = match genesis {
+        Ok(Some(ref genesis)) if genesis.header().entry_type() == dna_type => {
+            Err(HolochainError::new("the chain already has a DNA entry"))
+        }
+        Ok(_) => state.chain.push_entry(&entry),
+        Err(e) => Err(e),
+    };
+
+    // only public entries go to the DHT, private entries stay on the local chain
+    if let Ok(ref pair) = response {
+        if pair.is_public() {
+            state.publish_queue.push_back(pair.entry().clone());
+        }
+    }
+
+    state.insert_action(action_wrapper, ActionResponse::Commit(response));
+    Vec::new()
 }
 
 /// do a get action against an agent state
 /// intended for use inside the reducer, isolated for unit testing
 fn reduce_get(
-    _context: Arc<Context>,
+    context: Arc<Context>,
     state: &mut AgentState,
     action_wrapper: &ActionWrapper,
     _action_channel: &Sender<ActionWrapper>,
     _observer_channel: &Sender<Observer>,
-) {
+) -> Vec<ActionWrapper> {
     let action = action_wrapper.action();
     let key = unwrap_to!(action => Action::GetEntry);
 
-    let result = state.chain.entry(&key.clone());
+    let mut result = state.chain.entry(&key.clone());
 
-    // @TODO if the get fails local, do a network get
-    // @see https://github.com/holochain/holochain-rust/issues/167
+    // if the get fails local, do a network get
+    if let Ok(None) = result {
+        if let Some(ref network) = context.network {
+            result = network.get_entry(&key);
+        }
+    }
 
-    state.actions.insert(
-        action_wrapper.clone(),
-        ActionResponse::GetEntry(
-            result
-                .clone()
-                .expect("should be able to get entry that we just added"),
-        ),
-    );
+    state.insert_action(action_wrapper, ActionResponse::GetEntry(result));
+    Vec::new()
+}
+
+/// do a heartbeat action against an agent state
+/// the state is left as is, reducing is only needed so that observers run again
+fn reduce_heartbeat(
+    _context: Arc<Context>,
+    _state: &mut AgentState,
+    _action_wrapper: &ActionWrapper,
+    _action_channel: &Sender<ActionWrapper>,
+    _observer_channel: &Sender<Observer>,
+) -> Vec<ActionWrapper> {
+    Vec::new()
+}
+
+/// do a get links action against an agent state
+/// links are stored as PairMeta against the base Pair, the attribute is the entry type of the
+/// link and the value is the hash of the link target
+/// intended for use inside the reducer, isolated for unit testing
+fn reduce_get_links(
+    _context: Arc<Context>,
+    state: &mut AgentState,
+    action_wrapper: &ActionWrapper,
+    _action_channel: &Sender<ActionWrapper>,
+    _observer_channel: &Sender<Observer>,
+) -> Vec<ActionWrapper> {
+    let action = action_wrapper.action();
+    let (base, entry_type) = match action {
+        Action::GetLinks { base, entry_type } => (base, entry_type),
+        _ => unreachable!(),
+    };
+
+    let result = match state.chain.entry(&base) {
+        Ok(Some(base_pair)) => state.chain.table().metas_for_pair(&base_pair).map(|metas| {
+            metas
+                .iter()
+                .filter(|m| &m.attribute() == entry_type)
+                .map(|m| m.value())
+                .collect()
+        }),
+        Ok(None) => Err(HolochainError::new(&format!(
+            "base entry {} not found for get links",
+            base
+        ))),
+        Err(err) => Err(err),
+    };
+
+    state.insert_action(action_wrapper, ActionResponse::GetLinks(result));
+    Vec::new()
 }
 
 /// maps incoming action to the correct handler
@@ -133,11 +328,15 @@ fn resolve_reducer(action_wrapper: &ActionWrapper) -> Option<AgentReduceFn> {
     match action_wrapper.action() {
         Action::Commit(_) => Some(reduce_commit),
         Action::GetEntry(_) => Some(reduce_get),
+        Action::GetLinks { .. } => Some(reduce_get_links),
+        Action::Heartbeat => Some(reduce_heartbeat),
         _ => None,
     }
 }
 
 /// Reduce Agent's state according to provided Action
+/// follow-up actions emitted by the handler are sent to the action channel, so the instance
+/// loop reduces them after this action
 pub fn reduce(
     context: Arc<Context>,
     old_state: Arc<AgentState>,
@@ -145,33 +344,158 @@ pub fn reduce(
     action_channel: &Sender<ActionWrapper>,
     observer_channel: &Sender<Observer>,
 ) -> Arc<AgentState> {
-    let handler = resolve_reducer(action_wrapper);
-    match handler {
-        Some(f) => {
-            let mut new_state: AgentState = (*old_state).clone();
-            f(
-                context,
-                &mut new_state,
-                &action_wrapper,
-                action_channel,
-                observer_channel,
+    let (new_state, follow_ups) = apply(
+        context,
+        old_state,
+        action_wrapper,
+        action_channel,
+        observer_channel,
+    );
+    for follow_up in follow_ups {
+        action_channel
+            .send(follow_up)
+            .expect("action channel to be open in reducer");
+    }
+    new_state
+}
+
+/// Reduce Agent's state according to provided Action without dispatching anything
+/// returns the new state and the follow-up actions emitted by the handler
+pub fn apply(
+    context: Arc<Context>,
+    old_state: Arc<AgentState>,
+    action_wrapper: &ActionWrapper,
+    action_channel: &Sender<ActionWrapper>,
+    observer_channel: &Sender<Observer>,
+) -> (Arc<AgentState>, Vec<ActionWrapper>) {
+    match resolve_reducer(action_wrapper) {
+        Some(f) => apply_handler(
+            f,
+            context,
+            &old_state,
+            action_wrapper,
+            action_channel,
+            observer_channel,
+        ),
+        None => (old_state, Vec::new()),
+    }
+}
+
+/// best effort message of a caught panic, panics carry either a &str or a String
+fn panic_message(payload: &(Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_else(|| "unknown panic".to_string()),
+    }
+}
+
+/// runs a handler against a copy of the state, logging the action if the context asks for it
+/// a panicking handler is contained to its action: the state is left as it was apart from a
+/// Failed response stored for the action, so the instance keeps processing later actions
+/// the chain is restored to its top from before the handler, Pairs already pushed stay in the
+/// table but are unreachable from the chain
+fn apply_handler(
+    f: AgentReduceFn,
+    context: Arc<Context>,
+    old_state: &AgentState,
+    action_wrapper: &ActionWrapper,
+    action_channel: &Sender<ActionWrapper>,
+    observer_channel: &Sender<Observer>,
+) -> (Arc<AgentState>, Vec<ActionWrapper>) {
+    let name = action_wrapper.action_name();
+    if context.log_actions {
+        // a failing logger should never prevent an action from reducing
+        let _ = context.log(&format!("reducing {}", name));
+    }
+
+    let checkpoint = old_state.chain.checkpoint();
+    let mut new_state: AgentState = old_state.clone();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        f(
+            Arc::clone(&context),
+            &mut new_state,
+            &action_wrapper,
+            action_channel,
+            observer_channel,
+        )
+    }));
+    let follow_ups = match result {
+        Ok(follow_ups) => follow_ups,
+        Err(payload) => {
+            let message = format!(
+                "reducer for {} panicked: {}",
+                name,
+                panic_message(&*payload)
+            );
+            let _ = context.log(&message);
+            // the handler may have left the copy half updated and pushed to the shared chain
+            if let Err(e) = old_state.chain.restore(&checkpoint) {
+                let _ = context.log(&format!(
+                    "could not roll back the chain after {} panicked: {}",
+                    name, e
+                ));
+            }
+            new_state = old_state.clone();
+            new_state.insert_action(
+                action_wrapper,
+                ActionResponse::Failed(HolochainError::new(&message)),
             );
-            Arc::new(new_state)
+            Vec::new()
         }
-        None => old_state,
+    };
+
+    if context.log_actions {
+        let outcome = match new_state.actions.get(action_wrapper).map(|t| &t.response) {
+            Some(ActionResponse::Commit(Err(_)))
+            | Some(ActionResponse::GetEntry(Err(_)))
+            | Some(ActionResponse::GetLinks(Err(_)))
+            | Some(ActionResponse::Failed(_)) => "error",
+            _ => "ok",
+        };
+        let _ = context.log(&format!("reduced {}: {}", name, outcome));
     }
+
+    (Arc::new(new_state), follow_ups)
 }
 
 #[cfg(test)]
 pub mod tests {
-    use super::{reduce_commit, reduce_get, ActionResponse, AgentState};
-    use action::tests::{test_action_wrapper_commit, test_action_wrapper_get};
-    use chain::tests::test_chain;
+    use super::{
+        apply, apply_handler, reduce, reduce_commit, reduce_get, reduce_get_links, ActionResponse,
+        AgentState,
+    };
+    use action::{
+        tests::{test_action_wrapper_commit, test_action_wrapper_get},
+        Action, ActionWrapper,
+    };
+    use agent::keys::tests::test_keys;
+    use chain::{tests::test_chain, SourceChain};
+    use chrono::{DateTime, Utc};
+    use context::Context;
     use error::HolochainError;
-    use hash_table::pair::tests::test_pair;
-    use instance::tests::{test_context, test_instance_blank};
+    use hash_table::{
+        actor::tests::test_table_actor,
+        entry::tests::{test_entry, test_entry_b, test_entry_unique},
+        pair::{tests::test_pair, Pair},
+        pair_meta::PairMeta,
+        sys_entry::ToEntry,
+        HashTable,
+    };
+    use holochain_dna::{zome::entry_types::Sharing, Dna};
+    use instance::{
+        tests::{test_context, test_context_and_logger, test_instance_blank},
+        Observer,
+    };
     use json::ToJson;
-    use std::{collections::HashMap, sync::Arc};
+    use key::Key;
+    use network::Network;
+    use std::{
+        collections::{HashMap, VecDeque},
+        sync::{mpsc::Sender, Arc},
+    };
 
     /// dummy agent state
     pub fn test_agent_state() -> AgentState {
@@ -185,7 +509,7 @@ pub mod tests {
 
     /// dummy action response for a successful get as test_pair()
     pub fn test_action_response_get() -> ActionResponse {
-        ActionResponse::GetEntry(Some(test_pair()))
+        ActionResponse::GetEntry(Ok(Some(test_pair())))
     }
 
     #[test]
@@ -200,12 +524,145 @@ pub mod tests {
         assert_eq!(None, test_agent_state().keys());
     }
 
+    #[test]
+    /// test round tripping an agent state through a snapshot
+    fn agent_state_snapshot_round_trip() {
+        let mut state = test_agent_state();
+        state.keys = Some(test_keys());
+        for e in vec![test_entry(), test_entry_b()] {
+            state
+                .chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+
+        let snapshot = state
+            .to_snapshot()
+            .expect("a valid agent state should snapshot");
+        assert_eq!(Some(test_keys().public_key()), snapshot.public_key);
+
+        let restored = AgentState::from_snapshot(test_table_actor(), &snapshot)
+            .expect("a snapshot should restore");
+        assert_eq!(state.chain(), restored.chain());
+        assert_eq!(None, restored.keys());
+        assert!(restored.actions().is_empty());
+    }
+
+    #[test]
+    /// test resetting the chain of an agent state
+    fn agent_state_reset_chain() {
+        let mut state = test_agent_state();
+        state.keys = Some(test_keys());
+        state
+            .chain
+            .push_entry(&test_entry())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        state.insert_action(&test_action_wrapper_get(), test_action_response_get());
+
+        let chain = test_chain();
+        state.reset_chain(&chain);
+
+        assert_eq!(None, state.chain().top_pair());
+        assert!(state.actions().is_empty());
+        assert_eq!(Some(test_keys()), state.keys());
+    }
+
+    #[test]
+    /// test pruning action responses by the time they were stored
+    fn agent_state_prune_actions_older_than() {
+        let mut state = test_agent_state();
+        let at = |s: &str| s.parse::<DateTime<Utc>>().expect("test time should parse");
+
+        let old = test_action_wrapper_get();
+        let new = test_action_wrapper_get();
+        state.insert_action_at(&old, test_action_response_get(), at("2018-01-01T00:00:00Z"));
+        state.insert_action_at(&new, test_action_response_get(), at("2018-03-01T00:00:00Z"));
+
+        state.prune_actions_older_than(at("2018-02-01T00:00:00Z"));
+
+        let actions = state.actions();
+        assert_eq!(1, actions.len());
+        assert!(actions.contains_key(&new));
+    }
+
+    #[test]
+    /// test that stored responses carry the time they were produced
+    fn agent_state_actions_with_time() {
+        let mut state = test_agent_state();
+        let instance = test_instance_blank();
+        let action_wrapper = test_action_wrapper_commit();
+
+        reduce_commit(
+            test_context("bob"),
+            &mut state,
+            &action_wrapper,
+            &instance.action_channel().clone(),
+            &instance.observer_channel().clone(),
+        );
+
+        let timestamped = state
+            .actions_with_time()
+            .get(&action_wrapper)
+            .expect("the commit response should be stored")
+            .clone();
+        assert_eq!(test_action_response_commit(), timestamped.response);
+        assert!((Utc::now() - timestamped.at).num_seconds().abs() < 1);
+    }
+
     #[test]
     /// test for the agent state actions getter
     fn agent_state_actions() {
         assert_eq!(HashMap::new(), test_agent_state().actions());
     }
 
+    #[test]
+    /// test the action count and commit success rate metrics
+    fn agent_state_metrics() {
+        let mut state = test_agent_state();
+        let instance = test_instance_blank();
+        assert_eq!(0, state.action_count());
+        assert_eq!(None, state.commit_success_rate());
+
+        // the second DNA entry fails to commit
+        let dna_entry = Dna::new().to_entry();
+        for entry in vec![dna_entry.clone(), dna_entry, test_entry_unique()] {
+            reduce_commit(
+                test_context("bob"),
+                &mut state,
+                &ActionWrapper::new(Action::Commit(entry)),
+                &instance.action_channel().clone(),
+                &instance.observer_channel().clone(),
+            );
+        }
+        state.insert_action(&test_action_wrapper_get(), test_action_response_get());
+
+        assert_eq!(4, state.action_count());
+        assert_eq!(Some(2.0 / 3.0), state.commit_success_rate());
+    }
+
+    #[test]
+    /// test that committing public entries queues them for publishing
+    fn test_reduce_commit_publish_queue() {
+        let mut state = test_agent_state();
+        let instance = test_instance_blank();
+        let private = test_entry_b().with_sharing(Sharing::Private);
+
+        for entry in vec![test_entry(), private] {
+            reduce_commit(
+                test_context("bob"),
+                &mut state,
+                &ActionWrapper::new(Action::Commit(entry)),
+                &instance.action_channel().clone(),
+                &instance.observer_channel().clone(),
+            );
+        }
+
+        assert_eq!(
+            &VecDeque::from(vec![test_entry()]),
+            state.pending_publishes()
+        );
+    }
+
     #[test]
     /// test for reducing commit
     fn test_reduce_commit() {
@@ -228,6 +685,196 @@ pub mod tests {
         );
     }
 
+    #[test]
+    /// test that a second DNA entry can't be committed
+    fn test_reduce_commit_dna_once() {
+        let mut state = test_agent_state();
+        let instance = test_instance_blank();
+        let dna_entry = Dna::new().to_entry();
+
+        let aw1 = ActionWrapper::new(Action::Commit(dna_entry.clone()));
+        reduce_commit(
+            test_context("bob"),
+            &mut state,
+            &aw1,
+            &instance.action_channel().clone(),
+            &instance.observer_channel().clone(),
+        );
+        let pair = unwrap_to!(state.actions().get(&aw1).unwrap() => ActionResponse::Commit)
+            .clone()
+            .expect("committing the first DNA entry shouldn't fail");
+
+        let aw2 = ActionWrapper::new(Action::Commit(dna_entry));
+        reduce_commit(
+            test_context("bob"),
+            &mut state,
+            &aw2,
+            &instance.action_channel().clone(),
+            &instance.observer_channel().clone(),
+        );
+        assert_eq!(
+            state.actions().get(&aw2),
+            Some(&ActionResponse::Commit(Err(HolochainError::new(
+                "the chain already has a DNA entry"
+            )))),
+        );
+        assert_eq!(
+            vec![pair],
+            state.chain().clone().into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    /// commit handler that asks for the committed entry to be fetched afterwards
+    fn reduce_commit_then_get(
+        context: Arc<Context>,
+        state: &mut AgentState,
+        action_wrapper: &ActionWrapper,
+        action_channel: &Sender<ActionWrapper>,
+        observer_channel: &Sender<Observer>,
+    ) -> Vec<ActionWrapper> {
+        reduce_commit(
+            context,
+            state,
+            action_wrapper,
+            action_channel,
+            observer_channel,
+        );
+        let entry = unwrap_to!(action_wrapper.action() => Action::Commit);
+        vec![ActionWrapper::new(Action::GetEntry(entry.hash()))]
+    }
+
+    /// handler that always panics
+    fn reduce_panic(
+        _context: Arc<Context>,
+        _state: &mut AgentState,
+        _action_wrapper: &ActionWrapper,
+        _action_channel: &Sender<ActionWrapper>,
+        _observer_channel: &Sender<Observer>,
+    ) -> Vec<ActionWrapper> {
+        panic!("deliberate test panic")
+    }
+
+    #[test]
+    /// test that a panicking handler fails its action without changing the state
+    fn test_apply_panic() {
+        let instance = test_instance_blank();
+        let (context, logger) = test_context_and_logger("bob");
+        let action_wrapper = test_action_wrapper_commit();
+        let message = "reducer for Commit panicked: deliberate test panic";
+
+        let (state, follow_ups) = apply_handler(
+            reduce_panic,
+            context,
+            &test_agent_state(),
+            &action_wrapper,
+            &instance.action_channel(),
+            &instance.observer_channel(),
+        );
+        assert_eq!(
+            Some(&ActionResponse::Failed(HolochainError::new(message))),
+            state.actions().get(&action_wrapper),
+        );
+        assert_eq!(None, state.chain().top_pair());
+        assert!(follow_ups.is_empty());
+        assert!(logger
+            .lock()
+            .expect("test logger shouldn't be poisoned")
+            .log
+            .contains(&message.to_string()));
+
+        // later actions still reduce
+        let (state, _) = apply(
+            test_context("bob"),
+            state,
+            &action_wrapper,
+            &instance.action_channel(),
+            &instance.observer_channel(),
+        );
+        assert_eq!(
+            Some(&test_action_response_commit()),
+            state.actions().get(&action_wrapper),
+        );
+    }
+
+    /// handler that pushes to the chain and then panics
+    fn reduce_push_then_panic(
+        _context: Arc<Context>,
+        state: &mut AgentState,
+        _action_wrapper: &ActionWrapper,
+        _action_channel: &Sender<ActionWrapper>,
+        _observer_channel: &Sender<Observer>,
+    ) -> Vec<ActionWrapper> {
+        state
+            .chain
+            .push_entry(&test_entry_unique())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        panic!("deliberate test panic")
+    }
+
+    #[test]
+    /// test that a handler panicking after pushing to the chain leaves the chain as it was
+    fn test_apply_panic_after_push() {
+        let instance = test_instance_blank();
+        let action_wrapper = test_action_wrapper_commit();
+        let mut old_state = test_agent_state();
+        let pair = old_state
+            .chain
+            .push_entry(&test_entry())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        let (state, _) = apply_handler(
+            reduce_push_then_panic,
+            test_context("bob"),
+            &old_state,
+            &action_wrapper,
+            &instance.action_channel(),
+            &instance.observer_channel(),
+        );
+        assert_eq!(
+            Some(&ActionResponse::Failed(HolochainError::new(
+                "reducer for Commit panicked: deliberate test panic"
+            ))),
+            state.actions().get(&action_wrapper),
+        );
+        assert_eq!(Some(pair.clone()), state.chain().top_pair());
+        assert_eq!(Some(pair), old_state.chain().top_pair());
+        assert_eq!(1, state.chain().clone().into_iter().count());
+    }
+
+    #[test]
+    /// test that applying an action returns the follow-up actions of its handler
+    fn test_apply_follow_ups() {
+        let instance = test_instance_blank();
+        let action_wrapper = test_action_wrapper_commit();
+
+        let (state, follow_ups) = apply_handler(
+            reduce_commit_then_get,
+            test_context("bob"),
+            &test_agent_state(),
+            &action_wrapper,
+            &instance.action_channel(),
+            &instance.observer_channel(),
+        );
+        assert_eq!(
+            Some(&test_action_response_commit()),
+            state.actions().get(&action_wrapper),
+        );
+        assert_eq!(
+            vec![&Action::GetEntry(test_entry().hash())],
+            follow_ups.iter().map(|aw| aw.action()).collect::<Vec<_>>(),
+        );
+
+        // the built in handlers don't emit anything
+        let (_, follow_ups) = apply(
+            test_context("bob"),
+            Arc::new(test_agent_state()),
+            &action_wrapper,
+            &instance.action_channel(),
+            &instance.observer_channel(),
+        );
+        assert!(follow_ups.is_empty());
+    }
+
     #[test]
     /// test for reducing get
     fn test_reduce_get() {
@@ -248,7 +895,7 @@ pub mod tests {
         // nothing has been committed so the get must be None
         assert_eq!(
             state.actions().get(&aw1),
-            Some(&ActionResponse::GetEntry(None)),
+            Some(&ActionResponse::GetEntry(Ok(None))),
         );
 
         // do a round trip
@@ -272,6 +919,208 @@ pub mod tests {
         assert_eq!(state.actions().get(&aw2), Some(&test_action_response_get()),);
     }
 
+    /// network that holds a single Pair
+    struct TestNetwork {
+        pair: Pair,
+    }
+
+    impl Network for TestNetwork {
+        fn get_entry(&self, entry_hash: &str) -> Result<Option<Pair>, HolochainError> {
+            if self.pair.entry().hash() == entry_hash {
+                Ok(Some(self.pair.clone()))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    #[test]
+    /// test that reducing a get falls back to the network for entries missing locally
+    fn test_reduce_get_network() {
+        let mut state = test_agent_state();
+        let context =
+            Arc::new(test_context("foo").with_network(Arc::new(TestNetwork { pair: test_pair() })));
+        let instance = test_instance_blank();
+
+        let aw = test_action_wrapper_get();
+        reduce_get(
+            context,
+            &mut state,
+            &aw,
+            &instance.action_channel().clone(),
+            &instance.observer_channel().clone(),
+        );
+
+        assert_eq!(state.actions().get(&aw), Some(&test_action_response_get()));
+        assert_eq!(None, state.chain().top_pair());
+    }
+
+    /// network that can't be reached
+    struct FailingNetwork;
+
+    impl Network for FailingNetwork {
+        fn get_entry(&self, _entry_hash: &str) -> Result<Option<Pair>, HolochainError> {
+            Err(HolochainError::new("network unreachable"))
+        }
+    }
+
+    #[test]
+    /// test that a failing network get is the response of the get rather than a missing entry
+    fn test_reduce_get_network_error() {
+        let mut state = test_agent_state();
+        let context = Arc::new(test_context("foo").with_network(Arc::new(FailingNetwork)));
+        let instance = test_instance_blank();
+
+        let aw = test_action_wrapper_get();
+        reduce_get(
+            context,
+            &mut state,
+            &aw,
+            &instance.action_channel().clone(),
+            &instance.observer_channel().clone(),
+        );
+
+        assert_eq!(
+            state.actions().get(&aw),
+            Some(&ActionResponse::GetEntry(Err(HolochainError::new(
+                "network unreachable"
+            )))),
+        );
+    }
+
+    #[test]
+    /// test for reducing get links
+    fn test_reduce_get_links() {
+        let mut state = test_agent_state();
+        let context = test_context("foo");
+
+        let instance = test_instance_blank();
+
+        // commit the base entry to link from
+        reduce_commit(
+            Arc::clone(&context),
+            &mut state,
+            &test_action_wrapper_commit(),
+            &instance.action_channel().clone(),
+            &instance.observer_channel().clone(),
+        );
+        let base_pair = state
+            .chain()
+            .top_pair()
+            .expect("committed pair should be the top pair");
+
+        let get_links = || {
+            ActionWrapper::new(Action::GetLinks {
+                base: test_entry().key(),
+                entry_type: "testLinkType".to_string(),
+            })
+        };
+
+        // no links yet
+        let aw1 = get_links();
+        reduce_get_links(
+            Arc::clone(&context),
+            &mut state,
+            &aw1,
+            &instance.action_channel().clone(),
+            &instance.observer_channel().clone(),
+        );
+        assert_eq!(
+            state.actions().get(&aw1),
+            Some(&ActionResponse::GetLinks(Ok(vec![]))),
+        );
+
+        // link the base to a target
+        state
+            .chain()
+            .table()
+            .assert_pair_meta(&PairMeta::new(
+                &test_keys(),
+                &base_pair,
+                "testLinkType",
+                &test_entry_b().key(),
+            ))
+            .expect("asserting metadata shouldn't fail");
+
+        let aw2 = get_links();
+        reduce_get_links(
+            Arc::clone(&context),
+            &mut state,
+            &aw2,
+            &instance.action_channel().clone(),
+            &instance.observer_channel().clone(),
+        );
+        assert_eq!(
+            state.actions().get(&aw2),
+            Some(&ActionResponse::GetLinks(Ok(vec![test_entry_b().key()]))),
+        );
+    }
+
+    #[test]
+    /// test that reducing with log_actions enabled logs the action through the context logger
+    fn test_reduce_log_actions() {
+        let (context, logger) = test_context_and_logger("bob");
+        let mut context = (*context).clone();
+        context.log_actions = true;
+
+        let instance = test_instance_blank();
+
+        reduce(
+            Arc::new(context),
+            Arc::new(test_agent_state()),
+            &test_action_wrapper_commit(),
+            &instance.action_channel(),
+            &instance.observer_channel(),
+        );
+
+        let log = logger
+            .lock()
+            .expect("test logger shouldn't be poisoned")
+            .log
+            .clone();
+        assert_eq!(
+            vec![
+                "reducing Commit".to_string(),
+                "reduced Commit: ok".to_string(),
+            ],
+            log,
+        );
+    }
+
+    #[test]
+    /// test that a get links action that fails is logged as an error
+    fn test_reduce_log_actions_get_links_error() {
+        let (context, logger) = test_context_and_logger("bob");
+        let mut context = (*context).clone();
+        context.log_actions = true;
+
+        let instance = test_instance_blank();
+
+        reduce(
+            Arc::new(context),
+            Arc::new(test_agent_state()),
+            &ActionWrapper::new(Action::GetLinks {
+                base: test_entry().key(),
+                entry_type: "testLinkType".to_string(),
+            }),
+            &instance.action_channel(),
+            &instance.observer_channel(),
+        );
+
+        let log = logger
+            .lock()
+            .expect("test logger shouldn't be poisoned")
+            .log
+            .clone();
+        assert_eq!(
+            vec![
+                "reducing GetLinks".to_string(),
+                "reduced GetLinks: error".to_string(),
+            ],
+            log,
+        );
+    }
+
     #[test]
     /// test response to json
     fn test_response_to_json() {
@@ -280,7 +1129,7 @@ pub mod tests {
             ActionResponse::Commit(Ok(test_pair())).to_json().unwrap(),
         );
         assert_eq!(
-            "{\"error\":\"some error\"}",
+            "{\"error\":\"some error\",\"error_kind\":\"ErrorGeneric\"}",
             ActionResponse::Commit(Err(HolochainError::new("some error")))
                 .to_json()
                 .unwrap(),
@@ -288,8 +1137,27 @@ pub mod tests {
 
         assert_eq!(
             "{\"header\":{\"entry_type\":\"testEntryType\",\"timestamp\":\"\",\"link\":null,\"entry_hash\":\"QmbXSE38SN3SuJDmHKSSw5qWWegvU7oTxrLDRavWjyxMrT\",\"entry_signature\":\"\",\"link_same_type\":null},\"entry\":{\"content\":\"test entry content\",\"entry_type\":\"testEntryType\"}}",
-            ActionResponse::GetEntry(Some(test_pair())).to_json().unwrap(),
+            ActionResponse::GetEntry(Ok(Some(test_pair())))
+                .to_json()
+                .unwrap(),
+        );
+        assert_eq!("", ActionResponse::GetEntry(Ok(None)).to_json().unwrap());
+        assert_eq!(
+            "{\"error\":\"some error\",\"error_kind\":\"ErrorGeneric\"}",
+            ActionResponse::GetEntry(Err(HolochainError::new("some error")))
+                .to_json()
+                .unwrap(),
+        );
+
+        assert_eq!(
+            "[\"QmbXSE38SN3SuJDmHKSSw5qWWegvU7oTxrLDRavWjyxMrT\"]",
+            ActionResponse::GetLinks(Ok(vec![test_entry().key()]))
+                .to_json()
+                .unwrap(),
+        );
+        assert_eq!(
+            "[]",
+            ActionResponse::GetLinks(Ok(vec![])).to_json().unwrap()
         );
-        assert_eq!("", ActionResponse::GetEntry(None).to_json().unwrap());
     }
 }
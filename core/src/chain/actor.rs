@@ -1,8 +1,10 @@
 use actor::{AskSelf, Protocol, SYS};
 use error::HolochainError;
 use hash_table::pair::Pair;
+use key::Key;
 use riker::actors::*;
 use snowflake;
+use std::sync::mpsc::{channel, Receiver, Sender};
 
 /// anything that can be asked of Chain and block on responses
 /// needed to support implementing ask on upstream ActorRef from riker
@@ -11,6 +13,14 @@ pub trait AskChain {
     fn set_top_pair(&self, &Option<Pair>) -> Result<Option<Pair>, HolochainError>;
     /// Protocol::GetTopPair -> Protocol::GetTopPairResult
     fn top_pair(&self) -> Option<Pair>;
+    /// Protocol::GetGenesisHash -> Protocol::GetGenesisHashResult
+    fn genesis_hash(&self) -> Option<String>;
+    /// Protocol::ClearTopPair -> Protocol::ClearTopPairResult
+    fn clear_top_pair(&self) -> Result<(), HolochainError>;
+    /// Protocol::ResetTopPair -> Protocol::ResetTopPairResult
+    fn reset_top_pair(&self, &Option<Pair>, &Option<String>) -> Result<(), HolochainError>;
+    /// Protocol::SubscribeCommits -> Protocol::SubscribeCommitsResult
+    fn subscribe_commits(&self) -> Receiver<Pair>;
 }
 
 impl AskChain for ActorRef<Protocol> {
@@ -23,17 +33,57 @@ impl AskChain for ActorRef<Protocol> {
         let response = self.block_on_ask(Protocol::GetTopPair);
         unwrap_to!(response => Protocol::GetTopPairResult).clone()
     }
+
+    fn genesis_hash(&self) -> Option<String> {
+        let response = self.block_on_ask(Protocol::GetGenesisHash);
+        unwrap_to!(response => Protocol::GetGenesisHashResult).clone()
+    }
+
+    fn clear_top_pair(&self) -> Result<(), HolochainError> {
+        let response = self.block_on_ask(Protocol::ClearTopPair);
+        unwrap_to!(response => Protocol::ClearTopPairResult).clone()
+    }
+
+    fn reset_top_pair(
+        &self,
+        top_pair: &Option<Pair>,
+        genesis_hash: &Option<String>,
+    ) -> Result<(), HolochainError> {
+        let response = self.block_on_ask(Protocol::ResetTopPair {
+            top_pair: top_pair.clone(),
+            genesis_hash: genesis_hash.clone(),
+        });
+        unwrap_to!(response => Protocol::ResetTopPairResult).clone()
+    }
+
+    fn subscribe_commits(&self) -> Receiver<Pair> {
+        let (sender, receiver) = channel();
+        // block until subscribed so no pair set after this returns can be missed
+        self.block_on_ask(Protocol::SubscribeCommits(sender));
+        receiver
+    }
 }
 
+/// holds the top pair of a chain
+/// riker processes one message at a time, so top pair reads and writes are serialized across
+/// every clone of a Chain sharing this actor
 pub struct ChainActor {
     top_pair: Option<Pair>,
+    /// key of the first pair set on an empty chain, it never changes until the chain is cleared
+    genesis_hash: Option<String>,
+    /// every new top pair is sent to these, subscribers with a dropped receiver are removed
+    commit_subscribers: Vec<Sender<Pair>>,
 }
 
 impl ChainActor {
     /// returns a new ChainActor struct
     /// internal use for riker, use new_ref instead
     fn new() -> ChainActor {
-        ChainActor { top_pair: None }
+        ChainActor {
+            top_pair: None,
+            genesis_hash: None,
+            commit_subscribers: Vec::new(),
+        }
     }
 
     /// actor() for riker
@@ -53,6 +103,50 @@ impl ChainActor {
             &snowflake::ProcessUniqueId::new().to_string(),
         ).expect("could not create ChainActor in actor system")
     }
+
+    /// sets the top pair if it links to the current top pair
+    /// None always clears the top pair
+    fn set_top_pair(&mut self, pair: Option<Pair>) -> Result<Option<Pair>, HolochainError> {
+        if let Some(ref p) = pair {
+            let top_pair = self.top_pair.as_ref().map(|t| t.key());
+            let next_pair = p.header().link();
+            if top_pair != next_pair {
+                return Err(HolochainError::new(&format!(
+                    "top pair did not match next hash pair from pushed pair: {:?} vs. {:?}",
+                    top_pair, next_pair,
+                )));
+            }
+        }
+        if let Some(ref p) = pair {
+            self.commit_subscribers
+                .retain(|subscriber| subscriber.send(p.clone()).is_ok());
+        }
+        // a pair set on an empty chain doesn't link to anything so it is the genesis pair
+        if self.top_pair.is_none() || pair.is_none() {
+            self.genesis_hash = pair.as_ref().map(|p| p.key());
+        }
+        self.top_pair = pair;
+        Ok(self.top_pair.clone())
+    }
+
+    /// moves the top pair to any pair in one step, e.g. back to an older pair of the chain
+    /// unlike set_top_pair() nothing is checked against the current top pair and commit
+    /// subscribers are not notified, as no pair is being committed
+    /// the genesis hash can't be looked up without a table so it must be given with the pair
+    fn reset_top_pair(
+        &mut self,
+        top_pair: Option<Pair>,
+        genesis_hash: Option<String>,
+    ) -> Result<(), HolochainError> {
+        if top_pair.is_some() != genesis_hash.is_some() {
+            return Err(HolochainError::new(
+                "a genesis hash must be given for a top pair and only for a top pair",
+            ));
+        }
+        self.top_pair = top_pair;
+        self.genesis_hash = genesis_hash;
+        Ok(())
+    }
 }
 
 impl Actor for ChainActor {
@@ -67,11 +161,8 @@ impl Actor for ChainActor {
         sender
             .try_tell(
                 match message {
-                    // set the top pair to the value passed
-                    Protocol::SetTopPair(p) => {
-                        self.top_pair = p;
-                        Protocol::SetTopPairResult(Ok(self.top_pair.clone()))
-                    }
+                    // set the top pair to the value passed if it links to the current top pair
+                    Protocol::SetTopPair(p) => Protocol::SetTopPairResult(self.set_top_pair(p)),
 
                     // evaluates to the current top pair
                     Protocol::GetTopPair => {
@@ -79,6 +170,30 @@ impl Actor for ChainActor {
                         Protocol::GetTopPairResult(ret)
                     }
 
+                    // evaluates to the cached key of the genesis pair
+                    Protocol::GetGenesisHash => {
+                        Protocol::GetGenesisHashResult(self.genesis_hash.clone())
+                    }
+
+                    // resets the chain to empty
+                    Protocol::ClearTopPair => {
+                        self.top_pair = None;
+                        self.genesis_hash = None;
+                        Protocol::ClearTopPairResult(Ok(()))
+                    }
+
+                    // moves the top pair without link checks or commit notifications
+                    Protocol::ResetTopPair {
+                        top_pair,
+                        genesis_hash,
+                    } => Protocol::ResetTopPairResult(self.reset_top_pair(top_pair, genesis_hash)),
+
+                    // sends every following top pair to the passed sender
+                    Protocol::SubscribeCommits(subscriber) => {
+                        self.commit_subscribers.push(subscriber);
+                        Protocol::SubscribeCommitsResult
+                    }
+
                     _ => unreachable!(),
                 },
                 Some(context.myself()),
@@ -91,7 +206,9 @@ impl Actor for ChainActor {
 pub mod tests {
     use actor::Protocol;
     use chain::actor::{AskChain, ChainActor};
+    use error::HolochainError;
     use hash_table::pair::tests::{test_pair_a, test_pair_b};
+    use key::Key;
     use riker::actors::*;
 
     /// dummy chain actor reference
@@ -119,6 +236,11 @@ pub mod tests {
 
         assert_eq!(Some(pair_a.clone()), chain_actor.top_pair());
 
+        chain_actor
+            .set_top_pair(&None)
+            .expect("could not clear top pair");
+        assert_eq!(None, chain_actor.top_pair());
+
         let pair_b = test_pair_b();
         chain_actor
             .set_top_pair(&Some(pair_b.clone()))
@@ -127,4 +249,72 @@ pub mod tests {
         assert_eq!(Some(pair_b.clone()), chain_actor.top_pair());
     }
 
+    #[test]
+    /// a top pair that doesn't link to the current top pair is rejected
+    fn test_set_top_pair_link() {
+        let chain_actor = test_chain_actor();
+
+        let pair_a = test_pair_a();
+        chain_actor
+            .set_top_pair(&Some(pair_a.clone()))
+            .expect("could not set top pair a");
+
+        // pair b was built for an empty chain so it doesn't link to pair a
+        let pair_b = test_pair_b();
+        assert_eq!(
+            Err(HolochainError::new(&format!(
+                "top pair did not match next hash pair from pushed pair: {:?} vs. {:?}",
+                Some(pair_a.key()),
+                pair_b.header().link(),
+            ))),
+            chain_actor.set_top_pair(&Some(pair_b)),
+        );
+        assert_eq!(Some(pair_a), chain_actor.top_pair());
+    }
+
+    #[test]
+    /// can clear the top pair through the chain actor
+    fn test_clear_top_pair() {
+        let chain_actor = test_chain_actor();
+
+        chain_actor
+            .set_top_pair(&Some(test_pair_a()))
+            .expect("could not set top pair a");
+        assert_eq!(Ok(()), chain_actor.clear_top_pair());
+        assert_eq!(None, chain_actor.top_pair());
+
+        // clearing an empty chain is fine too
+        assert_eq!(Ok(()), chain_actor.clear_top_pair());
+    }
+
+    #[test]
+    /// the top pair can be moved to a pair that doesn't link to it without notifying anyone
+    fn test_reset_top_pair() {
+        let chain_actor = test_chain_actor();
+        let commits = chain_actor.subscribe_commits();
+
+        let pair_a = test_pair_a();
+        chain_actor
+            .set_top_pair(&Some(pair_a.clone()))
+            .expect("could not set top pair a");
+        assert_eq!(Ok(pair_a.clone()), commits.try_recv());
+
+        // pair b doesn't link to pair a but reset doesn't care
+        let pair_b = test_pair_b();
+        assert_eq!(
+            Ok(()),
+            chain_actor.reset_top_pair(&Some(pair_b.clone()), &Some(pair_b.key())),
+        );
+        assert_eq!(Some(pair_b.clone()), chain_actor.top_pair());
+        assert_eq!(Some(pair_b.key()), chain_actor.genesis_hash());
+        assert!(commits.try_recv().is_err());
+
+        // a top pair without a genesis hash is inconsistent
+        assert!(chain_actor.reset_top_pair(&Some(pair_a), &None).is_err());
+        assert_eq!(Some(pair_b), chain_actor.top_pair());
+
+        assert_eq!(Ok(()), chain_actor.reset_top_pair(&None, &None));
+        assert_eq!(None, chain_actor.top_pair());
+        assert_eq!(None, chain_actor.genesis_hash());
+    }
 }
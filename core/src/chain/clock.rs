@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+/// source of the time for the header timestamps of a Chain
+/// injected with Chain::deterministic(), e.g. a fixed time for reproducible tests
+pub trait Clock: Send + Sync {
+    /// returns the time to stamp a header built now with
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// any closure returning a time is a clock
+impl<F> Clock for F
+where
+    F: Fn() -> DateTime<Utc> + Send + Sync,
+{
+    fn now(&self) -> DateTime<Utc> {
+        self()
+    }
+}
+
+/// clocks are opaque, this lets Chain keep deriving Debug
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Clock")
+    }
+}
@@ -1,8 +1,10 @@
 use chain::{Chain, SourceChain};
-use hash;
+use error::HolochainError;
+use hash::HashAlgorithm;
 use hash_table::{entry::Entry, HashString};
+use json::{FromJson, ToJson};
 use key::Key;
-use multihash::Hash;
+use serde_json;
 
 /// Header of a source chain "Item"
 /// The hash of the Header is used as the Item's key in the source chain hash table
@@ -25,6 +27,9 @@ pub struct Header {
     entry_signature: String,
     /// Key to the most recent header of the same type, None is valid only for the first of that type
     link_same_type: Option<HashString>,
+    /// how the header is hashed, the same as for the entry
+    #[serde(default, skip_serializing_if = "HashAlgorithm::is_default")]
+    hash_algorithm: HashAlgorithm,
 }
 
 impl PartialEq for Header {
@@ -44,22 +49,32 @@ impl Header {
     /// @see chain::pair::Pair
     /// @see chain::entry::Entry
     pub fn new(chain: &Chain, entry: &Entry) -> Header {
-        Header {
-            entry_type: entry.entry_type().clone(),
-            // @TODO implement timestamps
-            // https://github.com/holochain/holochain-rust/issues/70
-            timestamp: String::new(),
-            link: chain.top_pair().as_ref().map(|p| p.header().hash()),
-            entry_hash: entry.hash().to_string(),
-            link_same_type: chain
-                .top_pair_type(&entry.entry_type())
-                // @TODO inappropriate expect()?
-                // @see https://github.com/holochain/holochain-rust/issues/147
-                .map(|p| p.header().hash()),
-            // @TODO implement signatures
-            // https://github.com/holochain/holochain-rust/issues/71
-            entry_signature: String::new(),
-        }
+        // @TODO implement timestamps for chains without a clock
+        // https://github.com/holochain/holochain-rust/issues/70
+        // @TODO implement signatures with chain.keys()
+        // https://github.com/holochain/holochain-rust/issues/71
+        let hash_algorithm = chain.hash_algorithm();
+        Header::builder()
+            .entry_type(&entry.entry_type())
+            .timestamp(&chain.timestamp())
+            .link(chain.top_pair().as_ref().map(|p| p.header().hash()))
+            .entry_hash(&entry.with_hash_algorithm(hash_algorithm).hash())
+            .link_same_type(
+                chain
+                    .top_pair_type(&entry.entry_type())
+                    // @TODO inappropriate expect()?
+                    // @see https://github.com/holochain/holochain-rust/issues/147
+                    .map(|p| p.header().hash()),
+            )
+            .hash_algorithm(hash_algorithm)
+            .build()
+            .expect("a header built from an entry always has an entry type and hash")
+    }
+
+    /// returns a HeaderBuilder for building a Header field by field
+    /// @see HeaderBuilder::build()
+    pub fn builder() -> HeaderBuilder {
+        HeaderBuilder::default()
     }
 
     /// entry_type getter
@@ -101,9 +116,7 @@ impl Header {
         ];
         let string_to_hash = pieces.concat();
 
-        // @TODO the hashing algo should not be hardcoded
-        // @see https://github.com/holochain/holochain-rust/issues/104
-        hash::str_to_b58_hash(&string_to_hash, Hash::SHA2256)
+        self.hash_algorithm.hash_str(&string_to_hash)
     }
 
     /// returns true if the header is valid
@@ -119,9 +132,99 @@ impl Key for Header {
     }
 }
 
+impl ToJson for Header {
+    fn to_json(&self) -> Result<String, HolochainError> {
+        Ok(serde_json::to_string(&self)?)
+    }
+}
+
+impl FromJson for Header {
+    fn from_json(s: &str) -> Result<Self, HolochainError> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+/// builds a Header with named setters rather than positional arguments
+/// the timestamp and signature default to empty and the links default to None
+#[derive(Clone, Debug, Default)]
+pub struct HeaderBuilder {
+    entry_type: Option<String>,
+    timestamp: String,
+    link: Option<HashString>,
+    entry_hash: Option<HashString>,
+    entry_signature: String,
+    link_same_type: Option<HashString>,
+    hash_algorithm: HashAlgorithm,
+}
+
+impl HeaderBuilder {
+    /// entry_type setter
+    pub fn entry_type(mut self, entry_type: &str) -> HeaderBuilder {
+        self.entry_type = Some(entry_type.to_string());
+        self
+    }
+
+    /// timestamp setter
+    pub fn timestamp(mut self, timestamp: &str) -> HeaderBuilder {
+        self.timestamp = timestamp.to_string();
+        self
+    }
+
+    /// link setter
+    pub fn link(mut self, link: Option<HashString>) -> HeaderBuilder {
+        self.link = link;
+        self
+    }
+
+    /// entry_hash setter
+    pub fn entry_hash(mut self, entry_hash: &str) -> HeaderBuilder {
+        self.entry_hash = Some(entry_hash.to_string());
+        self
+    }
+
+    /// entry_signature setter
+    pub fn signature(mut self, signature: &str) -> HeaderBuilder {
+        self.entry_signature = signature.to_string();
+        self
+    }
+
+    /// link_same_type setter
+    pub fn link_same_type(mut self, link_same_type: Option<HashString>) -> HeaderBuilder {
+        self.link_same_type = link_same_type;
+        self
+    }
+
+    /// hash_algorithm setter
+    pub fn hash_algorithm(mut self, hash_algorithm: HashAlgorithm) -> HeaderBuilder {
+        self.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    /// builds the Header
+    /// returns an error if the entry type or entry hash was never set
+    pub fn build(self) -> Result<Header, HolochainError> {
+        let entry_type = self
+            .entry_type
+            .ok_or_else(|| HolochainError::new("header is missing an entry type"))?;
+        let entry_hash = self
+            .entry_hash
+            .ok_or_else(|| HolochainError::new("header is missing an entry hash"))?;
+        Ok(Header {
+            entry_type,
+            timestamp: self.timestamp,
+            link: self.link,
+            entry_hash,
+            entry_signature: self.entry_signature,
+            link_same_type: self.link_same_type,
+            hash_algorithm: self.hash_algorithm,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chain::{header::Header, tests::test_chain, SourceChain};
+    use error::HolochainError;
     use hash_table::{entry::Entry, pair::tests::test_pair};
     use key::Key;
 
@@ -181,6 +284,51 @@ mod tests {
         assert!(h.validate());
     }
 
+    #[test]
+    /// tests for Header::builder()
+    fn builder() {
+        let h = Header::builder()
+            .entry_type("fooType")
+            .timestamp("2018-01-01T00:00:00Z")
+            .link(Some("QmLink".to_string()))
+            .entry_hash("QmEntry")
+            .signature("sig")
+            .link_same_type(Some("QmLinkSameType".to_string()))
+            .build()
+            .expect("a header with every field set should build");
+
+        assert_eq!("fooType", h.entry_type());
+        assert_eq!("2018-01-01T00:00:00Z", h.timestamp());
+        assert_eq!(Some("QmLink".to_string()), h.link());
+        assert_eq!("QmEntry", h.entry_hash());
+        assert_eq!("sig", h.entry_signature());
+        assert_eq!(Some("QmLinkSameType".to_string()), h.link_same_type());
+
+        // Header::new() builds the same header as before
+        let chain = test_chain();
+        let e = Entry::new("fooType", "foo");
+        assert_eq!(
+            Ok(Header::new(&chain, &e)),
+            Header::builder()
+                .entry_type("fooType")
+                .entry_hash(&e.hash())
+                .build()
+        );
+    }
+
+    #[test]
+    /// tests that Header::builder() requires an entry type and entry hash
+    fn builder_missing_fields() {
+        assert_eq!(
+            Err(HolochainError::new("header is missing an entry hash")),
+            Header::builder().entry_type("fooType").build()
+        );
+        assert_eq!(
+            Err(HolochainError::new("header is missing an entry type")),
+            Header::builder().entry_hash("QmEntry").build()
+        );
+    }
+
     #[test]
     /// tests for header.entry_type()
     fn entry_type() {
@@ -1,21 +1,48 @@
 pub mod actor;
 
-use actor::{AskSelf, Protocol};
-use chain::actor::{AskChain, ChainActor};
+use actor::Protocol;
+use agent::keys::Keys;
+use chain::{
+    actor::{AskChain, ChainActor},
+    clock::Clock,
+    header::Header,
+    validator::EntryValidator,
+};
+use chrono::{DateTime, Utc};
 use error::HolochainError;
-use hash_table::{entry::Entry, pair::Pair, HashTable};
+use hash::{self, HashAlgorithm};
+use hash_table::{entry::Entry, pair::Pair, sys_entry::EntryType, HashTable};
 use json::ToJson;
 use key::Key;
+use multihash::Hash;
 use riker::actors::*;
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
 use serde_json;
+#[cfg(feature = "no_panic")]
+use std::sync::Mutex;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    io::Read,
+    sync::{mpsc::Receiver, Arc},
+};
+pub mod clock;
 pub mod header;
+pub mod validator;
+
+/// number of Pairs put in or read from a table per batch while streaming chain JSON
+const STREAMING_BATCH_SIZE: usize = 100;
 
 /// Iterator type for pairs in a chain
 /// next method may panic if there is an error in the underlying table
+/// with the no_panic feature the error is kept in last_error() and iteration ends instead
 #[derive(Clone)]
 pub struct ChainIterator {
     table: ActorRef<Protocol>,
+    sys_table: Option<ActorRef<Protocol>>,
     current: Option<Pair>,
+    #[cfg(feature = "no_panic")]
+    last_error: Arc<Mutex<Option<HolochainError>>>,
 }
 
 impl ChainIterator {
@@ -25,23 +52,162 @@ impl ChainIterator {
         ChainIterator {
             current: pair.clone(),
             table: table.clone(),
+            sys_table: None,
+            #[cfg(feature = "no_panic")]
+            last_error: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// the table error that ended iteration early, if any
+    #[cfg(feature = "no_panic")]
+    pub fn last_error(&self) -> Option<HolochainError> {
+        self.last_error
+            .lock()
+            .ok()
+            .and_then(|last_error| last_error.clone())
+    }
+
+    /// May panic if there is an underlying error in the table
+    #[cfg(not(feature = "no_panic"))]
+    fn lookup(&self, key: &str) -> Option<Pair> {
+        // @TODO should this panic?
+        // @see https://github.com/holochain/holochain-rust/issues/146
+        find_pair(&self.table, &self.sys_table, key).expect("getting from a table shouldn't fail")
+    }
+
+    /// stores any underlying error in the table as last_error and returns None
+    #[cfg(feature = "no_panic")]
+    fn lookup(&self, key: &str) -> Option<Pair> {
+        match find_pair(&self.table, &self.sys_table, key) {
+            Ok(pair) => pair,
+            Err(error) => {
+                if let Ok(mut last_error) = self.last_error.lock() {
+                    *last_error = Some(error);
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Iterator type for the headers of the pairs in a chain, newest first
+/// only headers are looked up, MemTable and FileTable never read entry content while walking
+/// next method may panic if there is an error in the underlying table
+/// with the no_panic feature the error is kept in last_error() and iteration ends instead
+#[derive(Clone)]
+pub struct HeaderIterator {
+    table: ActorRef<Protocol>,
+    sys_table: Option<ActorRef<Protocol>>,
+    current: Option<Header>,
+    #[cfg(feature = "no_panic")]
+    last_error: Arc<Mutex<Option<HolochainError>>>,
+}
+
+impl HeaderIterator {
+    /// the table error that ended iteration early, if any
+    #[cfg(feature = "no_panic")]
+    pub fn last_error(&self) -> Option<HolochainError> {
+        self.last_error
+            .lock()
+            .ok()
+            .and_then(|last_error| last_error.clone())
+    }
+
+    /// May panic if there is an underlying error in the table
+    #[cfg(not(feature = "no_panic"))]
+    fn lookup(&self, key: &str) -> Option<Header> {
+        // @TODO should this panic?
+        // @see https://github.com/holochain/holochain-rust/issues/146
+        find_header(&self.table, &self.sys_table, key).expect("getting from a table shouldn't fail")
+    }
+
+    /// stores any underlying error in the table as last_error and returns None
+    #[cfg(feature = "no_panic")]
+    fn lookup(&self, key: &str) -> Option<Header> {
+        match find_header(&self.table, &self.sys_table, key) {
+            Ok(header) => header,
+            Err(error) => {
+                if let Ok(mut last_error) = self.last_error.lock() {
+                    *last_error = Some(error);
+                }
+                None
+            }
+        }
+    }
+}
+
+impl Iterator for HeaderIterator {
+    type Item = Header;
+
+    fn next(&mut self) -> Option<Header> {
+        let previous = self.current.take();
+        self.current = previous
+            .as_ref()
+            .and_then(|h| h.link())
+            .and_then(|key| self.lookup(&key));
+        previous
+    }
+}
+
+/// looks a Pair up in the table, then in the system table if there is one
+fn find_pair(
+    table: &ActorRef<Protocol>,
+    sys_table: &Option<ActorRef<Protocol>>,
+    key: &str,
+) -> Result<Option<Pair>, HolochainError> {
+    match (table.pair(key)?, sys_table) {
+        (None, Some(sys_table)) => sys_table.pair(key),
+        (pair, _) => Ok(pair),
+    }
+}
+
+/// looks many Pairs up in the table as one batch, then the missing ones in the system table
+/// results are positional, i.e. the nth result is the Pair option for the nth key
+fn find_pairs(
+    table: &ActorRef<Protocol>,
+    sys_table: &Option<ActorRef<Protocol>>,
+    keys: &[String],
+) -> Result<Vec<Option<Pair>>, HolochainError> {
+    let mut pairs = table.pairs(keys)?;
+    if let Some(sys_table) = sys_table {
+        let missing = keys
+            .iter()
+            .zip(pairs.iter())
+            .filter(|(_, pair)| pair.is_none())
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<String>>();
+        if !missing.is_empty() {
+            let mut found = sys_table.pairs(&missing)?.into_iter();
+            for pair in pairs.iter_mut().filter(|pair| pair.is_none()) {
+                *pair = found.next().unwrap_or(None);
+            }
         }
     }
+    Ok(pairs)
+}
+
+/// looks the Header of a Pair up in the table, then in the system table if there is one
+fn find_header(
+    table: &ActorRef<Protocol>,
+    sys_table: &Option<ActorRef<Protocol>>,
+    key: &str,
+) -> Result<Option<Header>, HolochainError> {
+    match (table.header(key)?, sys_table) {
+        (None, Some(sys_table)) => sys_table.header(key),
+        (header, _) => Ok(header),
+    }
 }
 
 impl Iterator for ChainIterator {
     type Item = Pair;
 
-    /// May panic if there is an underlying error in the table
+    /// May panic if there is an underlying error in the table (unless no_panic is enabled)
     fn next(&mut self) -> Option<Pair> {
         let previous = self.current.take();
-        self.current = previous.as_ref()
-                        .and_then(|p| p.header().link())
-                        // @TODO should this panic?
-                        // @see https://github.com/holochain/holochain-rust/issues/146
-                        .and_then(|h| {
-                            self.table.pair(&h.to_string()).expect("getting from a table shouldn't fail")
-                        });
+        self.current = previous
+            .as_ref()
+            .and_then(|p| p.header().link())
+            .and_then(|h| self.lookup(&h.to_string()));
         previous
     }
 }
@@ -50,22 +216,108 @@ impl Iterator for ChainIterator {
 pub struct Chain {
     actor: ActorRef<Protocol>,
     table: ActorRef<Protocol>,
+    /// table for Pairs with system entries and the headers of app Pairs, None to keep them in table
+    sys_table: Option<ActorRef<Protocol>>,
+    /// entry types whose content must parse as JSON to be pushed
+    json_entry_types: HashSet<String>,
+    /// validators run before pushing entries of their entry type
+    entry_validators: HashMap<String, Arc<EntryValidator>>,
+    /// how entries and headers pushed to this chain are hashed
+    hash_algorithm: HashAlgorithm,
+    /// byte length limit for the content of pushed entries, None for no limit
+    max_entry_size: Option<usize>,
+    /// time source for header timestamps, None for no timestamps
+    clock: Option<Arc<Clock>>,
+    /// keys of the agent this chain belongs to
+    /// only stored for now, headers are not signed yet
+    /// @see https://github.com/holochain/holochain-rust/issues/71
+    keys: Option<Keys>,
 }
 
 impl PartialEq for Chain {
     // @TODO can we just check the actors are equal? is actor equality a thing?
     // @see https://github.com/holochain/holochain-rust/issues/257
     fn eq(&self, other: &Chain) -> bool {
-        // an invalid chain is like NaN... not even equal to itself
-        self.validate() &&
-        other.validate() &&
-        // header hashing ensures that if the tops match the whole chain matches
-        self.top_pair() == other.top_pair()
+        match (self.top_pair(), other.top_pair()) {
+            (None, None) => true,
+            (None, _) | (_, None) => false,
+            // header hashing chains every older Pair into the key of the top Pair, so matching
+            // top keys over the same tables imply matching histories and nothing needs to be
+            // walked or validated
+            (Some(ref top), Some(ref other_top))
+                if top.key() == other_top.key()
+                    && self.table == other.table
+                    && self.sys_table == other.sys_table =>
+            {
+                true
+            }
+            // other tables may hold anything under the same keys, so fall back to validating and
+            // comparing every Pair
+            // an invalid chain is like NaN... not even equal to itself
+            _ => self.validate() && other.validate() && self.iter().eq(other.iter()),
+        }
     }
 }
 
 impl Eq for Chain {}
 
+/// self-contained copy of a chain for moving it between tables, e.g. across machines
+/// headers are included as system entries alongside the entries they point to
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChainBundle {
+    /// key of the top Pair, None for an empty chain
+    pub top: Option<String>,
+    /// header and content entries of every Pair reachable from the top
+    pub entries: Vec<Entry>,
+}
+
+/// the top Pair of a chain at some point, for restoring the chain to that point later
+/// @see Chain::checkpoint()
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChainCheckpoint {
+    top_pair: Option<Pair>,
+}
+
+impl ChainCheckpoint {
+    /// top_pair getter
+    pub fn top_pair(&self) -> Option<Pair> {
+        self.top_pair.clone()
+    }
+}
+
+/// reference to an entry in a chain that loads its content only when asked to
+/// @see Chain::entry_handles()
+#[derive(Clone, Debug)]
+pub struct EntryHandle {
+    entry_address: String,
+    entry_type: String,
+    pair_key: String,
+    table: ActorRef<Protocol>,
+    sys_table: Option<ActorRef<Protocol>>,
+}
+
+impl EntryHandle {
+    /// address of the entry content, i.e. the entry hash
+    pub fn entry_address(&self) -> &str {
+        &self.entry_address
+    }
+
+    /// entry_type getter
+    pub fn entry_type(&self) -> &str {
+        &self.entry_type
+    }
+
+    /// fetches the entry from the table
+    /// returns an error if the Pair of the entry is no longer in the table
+    pub fn load(&self) -> Result<Entry, HolochainError> {
+        find_pair(&self.table, &self.sys_table, &self.pair_key)?
+            .map(|pair| pair.entry().clone())
+            .ok_or_else(|| {
+                HolochainError::new(&format!("pair {} is not in the table", self.pair_key))
+            })
+    }
+}
+
 /// Turns a chain into an iterator over it's Pairs
 impl IntoIterator for Chain {
     type Item = Pair;
@@ -82,22 +334,740 @@ impl Chain {
         Chain {
             actor: ChainActor::new_ref(),
             table: table.clone(),
+            sys_table: None,
+            json_entry_types: HashSet::new(),
+            entry_validators: HashMap::new(),
+            hash_algorithm: HashAlgorithm::default(),
+            max_entry_size: None,
+            clock: None,
+            keys: None,
+        }
+    }
+
+    /// returns a new Chain whose headers are stamped by the given clock, so pushing the same
+    /// entries always builds byte-identical chains given a fixed clock e.g. for golden JSON tests
+    /// the keys are only stored until headers are signed, they don't change the headers yet
+    /// @see https://github.com/holochain/holochain-rust/issues/71
+    pub fn deterministic<C: Clock + 'static>(
+        table: ActorRef<Protocol>,
+        clock: C,
+        keys: Keys,
+    ) -> Chain {
+        Chain {
+            clock: Some(Arc::new(clock)),
+            keys: Some(keys),
+            ..Chain::new(table)
         }
     }
 
+    /// the timestamp for a header built now, from the clock of this chain
+    /// empty for a chain without a clock
+    /// @see https://github.com/holochain/holochain-rust/issues/70
+    pub fn timestamp(&self) -> String {
+        self.clock
+            .as_ref()
+            .map(|clock| clock.now().to_rfc3339())
+            .unwrap_or_default()
+    }
+
+    /// keys getter
+    pub fn keys(&self) -> Option<Keys> {
+        self.keys.clone()
+    }
+
+    /// returns this chain with entries and headers pushed from now on hashed by the given
+    /// algorithm, the default is a SHA-256 multihash
+    pub fn with_hash_algorithm(mut self, hash_algorithm: HashAlgorithm) -> Chain {
+        self.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    /// hash_algorithm getter
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.hash_algorithm
+    }
+
+    /// returns this chain with push_entry() rejecting entries whose content is over the given
+    /// number of bytes
+    pub fn with_max_entry_size(mut self, max_entry_size: usize) -> Chain {
+        self.max_entry_size = Some(max_entry_size);
+        self
+    }
+
+    /// returns this chain with the given entry types marked as JSON
+    /// push_entry() rejects entries of these types unless their content parses as JSON
+    pub fn with_json_entry_types(mut self, entry_types: &[&str]) -> Chain {
+        self.json_entry_types
+            .extend(entry_types.iter().map(|t| t.to_string()));
+        self
+    }
+
+    /// returns this chain with Pairs for system entries (e.g. DNA, agent id) pushed to the given
+    /// table from now on, leaving only app entries in the main table
+    /// the header of every app Pair is pushed to the given table too, without its content
+    /// lookups check both tables
+    pub fn with_sys_table(mut self, sys_table: ActorRef<Protocol>) -> Chain {
+        self.sys_table = Some(sys_table);
+        self
+    }
+
+    /// returns this chain with the validator registered for the given entry type
+    /// push_entry() rejects entries of that type that the validator returns an error for
+    /// entry types without a validator are not validated, registering again replaces it
+    pub fn with_entry_validator<V: EntryValidator + 'static>(
+        mut self,
+        entry_type: &str,
+        validator: V,
+    ) -> Chain {
+        self.entry_validators
+            .insert(entry_type.to_string(), Arc::new(validator));
+        self
+    }
+
     /// returns a reference to the underlying HashTable
     pub fn table(&self) -> ActorRef<Protocol> {
         self.table.clone()
     }
 
+    /// returns a reference to the HashTable for system entries, if any
+    pub fn sys_table(&self) -> Option<ActorRef<Protocol>> {
+        self.sys_table.clone()
+    }
+
+    /// returns a receiver for every Pair that becomes the top Pair from now on, in order
+    /// shared by every clone of this chain, dropping the receiver unsubscribes
+    pub fn subscribe_commits(&self) -> Receiver<Pair> {
+        self.actor.subscribe_commits()
+    }
+
+    /// returns the key of the oldest Pair in the chain, None for an empty chain
+    /// cached by the chain actor so nothing is walked
+    pub fn genesis_hash(&self) -> Option<String> {
+        self.actor.genesis_hash()
+    }
+
+    /// resets the chain to empty for every clone of it, e.g. for test teardown
+    /// the Pairs stay in the table
+    pub fn clear(&self) -> Result<(), HolochainError> {
+        self.actor.clear_top_pair()
+    }
+
     /// returns true if all pairs in the chain pass validation
     fn validate(&self) -> bool {
         self.iter().all(|p| p.validate())
     }
 
+    /// push_entry() then read the Pair back from the table
+    /// returns the pushed Pair and the Entry as stored in the table
+    /// returns an error if the Pair can't be read back, as the table is then inconsistent
+    pub fn push_entry_and_get(&mut self, entry: &Entry) -> Result<(Pair, Entry), HolochainError> {
+        let pair = self.push_entry(entry)?;
+        let stored = self.pair(&pair.key())?.ok_or_else(|| {
+            HolochainError::new(&format!(
+                "pushed pair {} is missing from the table",
+                pair.key()
+            ))
+        })?;
+        Ok((pair, stored.entry().clone()))
+    }
+
+    /// push_entry() that retries up to max_retries times if another clone of this chain moves
+    /// the top Pair while the entry is being pushed
+    /// the Pair is regenerated against the new top for every retry
+    /// returns the last error once the retries are used up
+    pub fn push_entry_with_retry(
+        &mut self,
+        entry: &Entry,
+        max_retries: usize,
+    ) -> Result<Pair, HolochainError> {
+        let mut retries = 0;
+        loop {
+            let top_pair = self.top_pair();
+            match self.push_entry(entry) {
+                Err(_) if retries < max_retries && self.top_pair() != top_pair => {
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// runs a custom validator against every Pair in the chain, newest first
+    /// the validator is given the chain too, e.g. for checking references to other Pairs
+    /// returns the first error returned by the validator
+    pub fn validate_with<F: Fn(&Pair, &Chain) -> Result<(), HolochainError>>(
+        &self,
+        validator: F,
+    ) -> Result<(), HolochainError> {
+        for p in self.iter() {
+            validator(&p, self)?;
+        }
+        Ok(())
+    }
+
+    /// runs a validator against every Entry in the chain, oldest first, e.g. to find entries
+    /// committed under older validation rules that the current rules reject
+    /// returns (entry hash, error) for every Entry the validator rejects, the chain is untouched
+    pub fn replay_validation<F: Fn(&Entry) -> Result<(), HolochainError>>(
+        &self,
+        validator: F,
+    ) -> Vec<(String, HolochainError)> {
+        let mut failures = Vec::new();
+        self.replay(|p| {
+            if let Err(err) = validator(p.entry()) {
+                failures.push((p.entry_address(), err));
+            }
+        });
+        failures
+    }
+
+    /// checks that every link and link_same_type in the chain resolves to a Pair in the table
+    /// returns an error naming the first dangling link, e.g. after a partial import
+    pub fn validate_no_orphan_links(&self) -> Result<(), HolochainError> {
+        self.validate_with(|p, chain| {
+            for link in vec![p.header().link(), p.header().link_same_type()] {
+                if let Some(key) = link {
+                    if chain.pair(&key)?.is_none() {
+                        return Err(HolochainError::new(&format!(
+                            "pair {} links to missing pair {}",
+                            p.key(),
+                            key
+                        )));
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// checks that every Pair in the chain has one of the allowed entry types, e.g. the entry
+    /// types declared by the zomes of the DNA, system entry types are always allowed
+    /// returns an error naming the first Pair with an undeclared entry type, newest first
+    pub fn validate_entry_types(&self, allowed: &[String]) -> Result<(), HolochainError> {
+        self.validate_with(|p, _| {
+            let entry_type = p.header().entry_type();
+            if p.entry().is_system_type() || allowed.iter().any(|t| t == entry_type) {
+                Ok(())
+            } else {
+                Err(HolochainError::ValidationError {
+                    field: Some("entry_type".to_string()),
+                    message: format!("pair {} has undeclared entry type {}", p.key(), entry_type),
+                })
+            }
+        })
+    }
+
+    /// checks that the chain starts with the DNA entry, as every well-formed chain must
+    /// returns an error for an empty chain or a genesis Pair of any other entry type
+    pub fn validate_genesis_is_dna(&self) -> Result<(), HolochainError> {
+        let genesis_hash = self
+            .genesis_hash()
+            .ok_or_else(|| HolochainError::new("the chain is empty so has no DNA genesis pair"))?;
+        let genesis_pair = self.pair(&genesis_hash)?.ok_or_else(|| {
+            HolochainError::new(&format!("genesis pair {} is missing", genesis_hash))
+        })?;
+        let entry_type = genesis_pair.header().entry_type();
+        if entry_type == EntryType::Dna.as_str() {
+            Ok(())
+        } else {
+            Err(HolochainError::new(&format!(
+                "genesis pair {} has entry type {} instead of the DNA type",
+                genesis_hash, entry_type
+            )))
+        }
+    }
+
+    /// recomputes every entry hash and header hash in the chain from scratch
+    /// returns an error naming the first Pair whose stored hashes don't match its content
+    pub fn verify_hashes(&self) -> Result<(), HolochainError> {
+        let mut next = self.top_pair().map(|p| p.key());
+        while let Some(key) = next {
+            let pair = self
+                .pair(&key)?
+                .ok_or_else(|| HolochainError::new(&format!("missing pair {}", key)))?;
+
+            let header_hash = pair.header().hash();
+            if header_hash != key {
+                return Err(HolochainError::new(&format!(
+                    "header hash mismatch for pair {}: header hashes to {}",
+                    key, header_hash,
+                )));
+            }
+
+            let entry_hash = pair.entry().hash();
+            if entry_hash != pair.header().entry_hash() {
+                return Err(HolochainError::new(&format!(
+                    "entry hash mismatch for pair {}: header has {} but entry hashes to {}",
+                    key,
+                    pair.header().entry_hash(),
+                    entry_hash,
+                )));
+            }
+
+            next = pair.header().link();
+        }
+        Ok(())
+    }
+
     /// returns a ChainIterator that provides cloned Pairs from the underlying HashTable
     fn iter(&self) -> ChainIterator {
-        ChainIterator::new(self.table(), &self.top_pair())
+        ChainIterator {
+            sys_table: self.sys_table(),
+            ..ChainIterator::new(self.table(), &self.top_pair())
+        }
+    }
+
+    /// returns a HeaderIterator over the headers of the Pairs in this chain, newest first
+    /// for walks that don't need entry content
+    /// with the no_panic feature a walk cut short by a table error is reported by last_error()
+    pub fn headers(&self) -> HeaderIterator {
+        HeaderIterator {
+            table: self.table(),
+            sys_table: self.sys_table(),
+            current: self.top_pair().map(|p| p.header().clone()),
+            #[cfg(feature = "no_panic")]
+            last_error: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// returns every Pair in the chain with its distance from the top, newest first
+    /// the top Pair has depth 0 and each link followed adds one
+    pub fn iter_with_depth(&self) -> impl Iterator<Item = (usize, Pair)> {
+        self.iter().enumerate()
+    }
+
+    /// returns the newest Pair that is present in both this chain and the other chain
+    /// walks this chain from the top so the first shared Pair found is the point of divergence
+    /// returns None if the chains share no history
+    pub fn common_ancestor(&self, other: &Chain) -> Option<Pair> {
+        let other_keys: HashSet<String> = other.iter().map(|p| p.key()).collect();
+        self.iter().find(|p| other_keys.contains(&p.key()))
+    }
+
+    /// returns a ChainBundle with the header and content entries of every Pair in this chain
+    pub fn export_bundle(&self) -> Result<ChainBundle, HolochainError> {
+        let mut entries = Vec::new();
+        for p in self.iter() {
+            let (header_entry, entry) = p.to_entry_pair();
+            entries.push(header_entry);
+            entries.push(entry);
+        }
+        Ok(ChainBundle {
+            top: self.top_pair().map(|p| p.key()),
+            entries,
+        })
+    }
+
+    /// returns a new Chain over the given table rebuilt from a ChainBundle
+    /// Pairs are rebuilt by following header links from the top, so they link up by construction
+    /// Pairs already in the table are found with one batch lookup, the rest are put in the table
+    /// as one batch and the top is set in one step
+    /// returns an error if a header or entry reachable from the top is missing from the bundle
+    pub fn import_bundle(
+        table: ActorRef<Protocol>,
+        bundle: &ChainBundle,
+    ) -> Result<Chain, HolochainError> {
+        let entries: HashMap<String, Entry> = bundle
+            .entries
+            .iter()
+            .map(|e| (e.hash(), e.clone()))
+            .collect();
+        let mut headers = HashMap::new();
+        for e in &bundle.entries {
+            if e.entry_type() == EntryType::Header.as_str() {
+                let header: Header = serde_json::from_str(&e.content())?;
+                headers.insert(header.hash(), header);
+            }
+        }
+
+        let mut pairs = Vec::new();
+        let mut next = bundle.top.clone();
+        while let Some(key) = next {
+            let header = headers.get(&key).ok_or_else(|| {
+                HolochainError::new(&format!("header {} is not in the bundle", key))
+            })?;
+            let entry = entries.get(header.entry_hash()).ok_or_else(|| {
+                HolochainError::new(&format!(
+                    "entry {} is not in the bundle",
+                    header.entry_hash()
+                ))
+            })?;
+            pairs.push(Pair::new(header, entry)?);
+            next = header.link();
+        }
+
+        let mut chain = Chain::new(table);
+        let keys = pairs.iter().map(|p| p.key()).collect::<Vec<String>>();
+        let missing = pairs
+            .iter()
+            .zip(chain.table.pairs(&keys)?)
+            .filter(|(_, stored)| stored.is_none())
+            .map(|(pair, _)| pair.clone())
+            .collect::<Vec<Pair>>();
+        chain.table.put_pairs(&missing)?;
+        chain
+            .actor
+            .reset_top_pair(&pairs.first().cloned(), &pairs.last().map(|p| p.key()))?;
+        Ok(chain)
+    }
+
+    /// import_bundle() for a ChainBundle serialized as JSON, e.g. received over the network
+    /// returns a SerializationError for JSON that isn't a ChainBundle
+    pub fn import_bundle_json(
+        table: ActorRef<Protocol>,
+        json: &str,
+    ) -> Result<Chain, HolochainError> {
+        let bundle: ChainBundle = serde_json::from_str(json)
+            .map_err(|e| HolochainError::from(e).with_context("while parsing chain bundle"))?;
+        Chain::import_bundle(table, &bundle)
+    }
+
+    /// returns a new Chain over the given table with every Pair of this chain replayed into it
+    /// Pairs are pushed oldest first so the new chain validates exactly as this chain does
+    /// this chain and its table are untouched
+    pub fn clone_into_table(&self, new_table: ActorRef<Protocol>) -> Result<Chain, HolochainError> {
+        let mut pairs = self.iter().collect::<Vec<Pair>>();
+        pairs.reverse();
+
+        let mut chain = Chain::new(new_table);
+        chain.json_entry_types = self.json_entry_types.clone();
+        chain.entry_validators = self.entry_validators.clone();
+        chain.hash_algorithm = self.hash_algorithm;
+        chain.max_entry_size = self.max_entry_size;
+        chain.clock = self.clock.clone();
+        chain.keys = self.keys.clone();
+        for p in pairs {
+            chain.push_pair(&p)?;
+        }
+        Ok(chain)
+    }
+
+    /// returns a single digest of the whole chain, newest Pair first
+    /// header links already chain the hashes so this hashes the concatenation of every Pair key
+    /// equal chains have equal roots and pushing any Pair changes the root
+    pub fn merkle_root(&self) -> String {
+        let keys = self.iter().map(|p| p.key()).collect::<Vec<String>>();
+        // @TODO the hashing algo should not be hardcoded
+        // @see https://github.com/holochain/holochain-rust/issues/104
+        hash::str_to_b58_hash(&keys.concat(), Hash::SHA2256)
+    }
+
+    /// returns every Pair pushed after the Pair with the given key, oldest first
+    /// the given Pair itself is not included
+    /// returns an error if the given key is not in this chain
+    pub fn entries_since(&self, pair_hash: &str) -> Result<Vec<Pair>, HolochainError> {
+        let mut pairs = Vec::new();
+        for p in self.iter() {
+            if p.key() == pair_hash {
+                pairs.reverse();
+                return Ok(pairs);
+            }
+            pairs.push(p);
+        }
+        Err(HolochainError::new(&format!(
+            "pair {} is not in the chain",
+            pair_hash
+        )))
+    }
+
+    /// returns up to n Pairs linked before the Pair with the given key, newest first
+    /// the given Pair itself is not included, fewer than n Pairs are returned near genesis
+    /// returns an error if the given key is not in this chain
+    pub fn ancestors_of(&self, pair_hash: &str, n: usize) -> Result<Vec<Pair>, HolochainError> {
+        let mut pairs = self.iter().skip_while(|p| p.key() != pair_hash);
+        match pairs.next() {
+            Some(_) => Ok(pairs.take(n).collect()),
+            None => Err(HolochainError::new(&format!(
+                "pair {} is not in the chain",
+                pair_hash
+            ))),
+        }
+    }
+
+    /// returns the Pairs that the header of the Pair with the given key links to
+    /// as (previous Pair, previous Pair of the same entry type), None for a link that is None
+    /// returns an error if the given key is not in the table
+    pub fn link_targets(
+        &self,
+        pair_hash: &str,
+    ) -> Result<(Option<Pair>, Option<Pair>), HolochainError> {
+        let pair = self.pair(pair_hash)?.ok_or_else(|| {
+            HolochainError::new(&format!("pair {} is not in the table", pair_hash))
+        })?;
+        let resolve = |link: Option<String>| match link {
+            Some(key) => self.pair(&key),
+            None => Ok(None),
+        };
+        Ok((
+            resolve(pair.header().link())?,
+            resolve(pair.header().link_same_type())?,
+        ))
+    }
+
+    /// attaches metadata to the entry of the Pair with the given key, e.g. after it was pushed
+    /// metadata is not hashed so the entry address and Pair key don't change and the chain stays
+    /// valid, the Pair is updated in whichever table holds it
+    /// returns the updated Pair, or an error if the given key is not in the table
+    pub fn set_entry_metadata(
+        &self,
+        pair_hash: &str,
+        metadata: serde_json::Value,
+    ) -> Result<Pair, HolochainError> {
+        let (pair, mut table) = match self.table.pair(pair_hash)? {
+            Some(pair) => (Some(pair), self.table()),
+            None => match self.sys_table() {
+                Some(sys_table) => (sys_table.pair(pair_hash)?, sys_table),
+                None => (None, self.table()),
+            },
+        };
+        let pair = pair.ok_or_else(|| {
+            HolochainError::new(&format!("pair {} is not in the table", pair_hash))
+        })?;
+
+        let annotated = Pair::new(pair.header(), &pair.entry().with_metadata(metadata))?;
+        table.put_pair(&annotated)?;
+        // the chain actor keeps its own copy of the top Pair
+        if self.top_pair().map(|p| p.key()) == Some(annotated.key()) {
+            self.actor
+                .reset_top_pair(&Some(annotated.clone()), &self.genesis_hash())?;
+        }
+        Ok(annotated)
+    }
+
+    /// returns the distinct entry addresses in the chain, newest first
+    /// the same content can be in several Pairs but its address is only listed once
+    /// @see https://github.com/holochain/holochain-rust/issues/145
+    pub fn entry_addresses(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.headers()
+            .map(|h| h.entry_hash().to_string())
+            .filter(|address| seen.insert(address.clone()))
+            .collect()
+    }
+
+    /// looks up many entries by entry address in a single pass over the chain headers, newest
+    /// first, then fetches the Pairs of the entries found as one batch
+    /// the walk stops early once every address is found, missing addresses are left out
+    /// may panic if there is an underlying error in the table
+    /// @see https://github.com/holochain/holochain-rust/issues/145
+    pub fn get_entries(&self, hashes: &[String]) -> HashMap<String, Entry> {
+        let mut wanted = hashes.iter().collect::<HashSet<&String>>();
+        let mut keys = Vec::new();
+        for h in self.headers() {
+            if wanted.is_empty() {
+                break;
+            }
+            if wanted.remove(&h.entry_hash().to_string()) {
+                keys.push(h.hash());
+            }
+        }
+
+        // @TODO should this panic?
+        // @see https://github.com/holochain/holochain-rust/issues/146
+        find_pairs(&self.table, &self.sys_table, &keys)
+            .expect("getting from a table shouldn't fail")
+            .into_iter()
+            .filter_map(|pair| pair.map(|p| (p.entry_address(), p.entry().clone())))
+            .collect()
+    }
+
+    /// returns a handle for the entry of every Pair in the chain, newest first
+    /// the handles are built from headers alone so no entry is read until EntryHandle::load()
+    pub fn entry_handles(&self) -> Vec<EntryHandle> {
+        self.headers()
+            .map(|h| EntryHandle {
+                entry_address: h.entry_hash().to_string(),
+                entry_type: h.entry_type().to_string(),
+                pair_key: h.hash(),
+                table: self.table(),
+                sys_table: self.sys_table(),
+            })
+            .collect()
+    }
+
+    /// returns the oldest Pair of the given entry type, i.e. when the type was first pushed
+    /// the counterpart of top_pair_type(), None if the chain has no Pair of the type
+    pub fn first_pair_of_type(&self, entry_type: &str) -> Option<Pair> {
+        self.iter()
+            .filter(|p| p.header().entry_type() == entry_type)
+            .last()
+    }
+
+    /// returns the Pairs from to_hash down to from_hash, both included, newest first
+    /// e.g. everything pushed between two checkpoints
+    /// returns an error if either Pair isn't in the chain or from_hash is newer than to_hash
+    pub fn entries_between(
+        &self,
+        from_hash: &str,
+        to_hash: &str,
+    ) -> Result<Vec<Pair>, HolochainError> {
+        let mut pairs = Vec::new();
+        for p in self.iter().skip_while(|p| p.key() != to_hash) {
+            let is_from = p.key() == from_hash;
+            pairs.push(p);
+            if is_from {
+                return Ok(pairs);
+            }
+        }
+
+        let missing = if pairs.is_empty() { to_hash } else { from_hash };
+        if self.iter().any(|p| p.key() == missing) {
+            Err(HolochainError::new(&format!(
+                "pair {} is newer than pair {}",
+                from_hash, to_hash
+            )))
+        } else {
+            Err(HolochainError::new(&format!(
+                "pair {} is not in the chain",
+                missing
+            )))
+        }
+    }
+
+    /// counts the Pairs in the chain whose header matches the predicate
+    /// only headers are looked up, no entry is read
+    pub fn count_matching<F: Fn(&Header) -> bool>(&self, predicate: F) -> usize {
+        self.headers().filter(predicate).count()
+    }
+
+    /// returns Pairs newest first until the first Pair that doesn't match the predicate
+    /// unlike filtering iter(), nothing older than the first non-matching Pair is read
+    pub fn take_while_pairs<F: Fn(&Pair) -> bool>(&self, predicate: F) -> Vec<Pair> {
+        self.iter().take_while(predicate).collect()
+    }
+
+    /// returns the newest Entry in the chain with the given type and content
+    /// complements entry() for callers that know an entry's content but not its hash
+    pub fn find_entry_by_content(&self, entry_type: &str, content: &str) -> Option<Entry> {
+        self.iter()
+            .find(|p| p.entry().entry_type() == entry_type && p.entry().content() == content)
+            .map(|p| p.entry().clone())
+    }
+
+    /// returns a new Chain over the same table holding only the oldest n Pairs of this chain
+    /// newer Pairs stay in the table but are unreachable from the new top
+    /// n >= length returns an equal chain and n == 0 returns an empty chain
+    pub fn truncate_to_length(&self, n: usize) -> Result<Chain, HolochainError> {
+        let pairs = self.iter().collect::<Vec<Pair>>();
+        let top_pair = match n {
+            0 => None,
+            _ => pairs.get(pairs.len().saturating_sub(n)).cloned(),
+        };
+        self.fork_at(top_pair)
+    }
+
+    /// returns a new Chain with the settings of this chain over the same table, with the given
+    /// Pair of this chain as its top Pair
+    /// the top is reset in one step, so nothing is walked up to from genesis
+    fn fork_at(&self, top_pair: Option<Pair>) -> Result<Chain, HolochainError> {
+        let chain = Chain {
+            actor: ChainActor::new_ref(),
+            ..self.clone()
+        };
+        let genesis_hash = top_pair.as_ref().and_then(|_| self.genesis_hash());
+        chain.actor.reset_top_pair(&top_pair, &genesis_hash)?;
+        Ok(chain)
+    }
+
+    /// returns a new Chain over the same table with the newest n Pairs of this chain dropped
+    /// n == 0 returns an equal chain and n >= length returns an empty chain
+    pub fn rewind(&self, n: usize) -> Result<Chain, HolochainError> {
+        self.fork_at(self.iter().nth(n))
+    }
+
+    /// captures the current top Pair so the chain can be restored to it, e.g. to roll back
+    /// several pushes when a later step of the same operation fails
+    pub fn checkpoint(&self) -> ChainCheckpoint {
+        ChainCheckpoint {
+            top_pair: self.top_pair(),
+        }
+    }
+
+    /// moves the top Pair of this chain and every clone of it back to the checkpoint
+    /// Pairs pushed since the checkpoint stay in the table but are unreachable from the top
+    /// returns an error if the checkpoint top is no longer in the chain, e.g. after a clear()
+    pub fn restore(&self, checkpoint: &ChainCheckpoint) -> Result<(), HolochainError> {
+        let genesis_hash = match checkpoint.top_pair {
+            Some(ref top_pair) => {
+                if !self.iter().any(|p| p.key() == top_pair.key()) {
+                    return Err(HolochainError::new(&format!(
+                        "checkpoint {} is not in the chain",
+                        top_pair.key()
+                    )));
+                }
+                self.genesis_hash()
+            }
+            None => None,
+        };
+
+        // a single reset so no clone ever sees a partially restored chain and restoring isn't
+        // reported to commit subscribers as new commits
+        self.actor
+            .reset_top_pair(&checkpoint.top_pair, &genesis_hash)
+    }
+
+    /// returns the newest Pair with a header timestamp at or before the given time
+    /// Pairs without a parseable RFC3339 timestamp are skipped
+    /// returns None if every timestamped Pair is newer than the given time
+    pub fn head_at_time(&self, at: &DateTime<Utc>) -> Option<Pair> {
+        self.iter().find(
+            |p| match DateTime::parse_from_rfc3339(p.header().timestamp()) {
+                Ok(t) => t.with_timezone(&Utc) <= *at,
+                Err(_) => false,
+            },
+        )
+    }
+
+    /// returns an error naming the first Pair whose header timestamp is earlier than the one
+    /// before it, walking oldest first
+    /// empty timestamps are unknown and skipped, other unparseable timestamps are an error
+    pub fn validate_monotonic_timestamps(&self) -> Result<(), HolochainError> {
+        let mut pairs = self.iter().collect::<Vec<Pair>>();
+        pairs.reverse();
+
+        let mut previous: Option<DateTime<Utc>> = None;
+        for p in pairs {
+            let timestamp = p.header().timestamp();
+            if timestamp.is_empty() {
+                continue;
+            }
+            let time = DateTime::parse_from_rfc3339(timestamp)
+                .map_err(|e| {
+                    HolochainError::new(&format!(
+                        "invalid timestamp {} for pair {}: {}",
+                        timestamp,
+                        p.key(),
+                        e
+                    ))
+                })?
+                .with_timezone(&Utc);
+            if let Some(previous) = previous {
+                if time < previous {
+                    return Err(HolochainError::new(&format!(
+                        "timestamp {} for pair {} is earlier than the previous pair",
+                        timestamp,
+                        p.key(),
+                    )));
+                }
+            }
+            previous = Some(time);
+        }
+        Ok(())
+    }
+
+    /// to_json() indented over several lines, for reading while debugging
+    /// use to_json() for anything that is compared or hashed
+    pub fn to_json_pretty(&self) -> Result<String, HolochainError> {
+        let as_seq = self.iter().collect::<Vec<Pair>>();
+        Ok(serde_json::to_string_pretty(&as_seq)?)
+    }
+
+    /// get the public pairs of the chain, top to bottom, as a JSON array, e.g. for publishing
+    /// to the DHT
+    /// same format as to_json() which includes private pairs too
+    pub fn to_public_json(&self) -> Result<String, HolochainError> {
+        let as_seq = self.iter().filter(|p| p.is_public()).collect::<Vec<Pair>>();
+        Ok(serde_json::to_string(&as_seq)?)
     }
 
     /// restore canonical JSON chain
@@ -109,14 +1079,85 @@ impl Chain {
         // @see https://github.com/holochain/holochain-rust/issues/168
         let mut as_seq: Vec<Pair> = serde_json::from_str(s).expect("argument should be valid json");
         as_seq.reverse();
+        assert!(as_seq.iter().all(|p| p.validate()), "pair should be valid");
 
         let mut chain = Chain::new(table);
-
+        chain
+            .table
+            .put_pairs(&as_seq)
+            .expect("pairs should be put in the table");
+        // set_top_pair() still checks every Pair links to the one before it
         for p in as_seq {
-            chain.push_pair(&p).expect("pair should be valid");
+            chain.set_top_pair(&Some(p)).expect("pair should be valid");
         }
         chain
     }
+
+    /// from_json() that reads the JSON from a reader without holding it all in memory
+    /// the array is newest first but pairs must be pushed oldest first, so this takes two passes:
+    /// parsed Pairs are put in the table in batches and only their keys are kept, then the keys
+    /// are walked in reverse, reading the Pairs back out of the table in batches to set each one
+    /// as the top Pair of the new chain
+    /// returns a SerializationError for JSON that isn't an array of Pairs
+    pub fn from_json_streaming<R: Read>(
+        table: ActorRef<Protocol>,
+        reader: R,
+    ) -> Result<Chain, HolochainError> {
+        let mut deserializer = serde_json::Deserializer::from_reader(reader);
+        let keys = deserializer
+            .deserialize_seq(PutPairsVisitor {
+                table: table.clone(),
+            })
+            .and_then(|keys| deserializer.end().map(|_| keys))
+            .map_err(|e| HolochainError::from(e).with_context("while parsing chain json"))?;
+
+        let chain = Chain::new(table);
+        let keys = keys.into_iter().rev().collect::<Vec<String>>();
+        for batch in keys.chunks(STREAMING_BATCH_SIZE) {
+            for (key, pair) in batch.iter().zip(chain.table.pairs(batch)?) {
+                let pair =
+                    pair.ok_or_else(|| HolochainError::new(&format!("missing pair {}", key)))?;
+                if !pair.validate() {
+                    return Err(HolochainError::new(
+                        "attempted to push an invalid pair for this chain",
+                    ));
+                }
+                chain
+                    .set_top_pair(&Some(pair))
+                    .map_err(|e| e.with_context("while setting top pair"))?;
+            }
+        }
+        Ok(chain)
+    }
+}
+
+/// serde visitor that puts the Pairs of a JSON array in the table in batches as they are parsed
+/// evaluates to the keys of the Pairs in array order
+struct PutPairsVisitor {
+    table: ActorRef<Protocol>,
+}
+
+impl<'de> Visitor<'de> for PutPairsVisitor {
+    type Value = Vec<String>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array of pairs")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<Vec<String>, A::Error> {
+        let mut keys = Vec::new();
+        let mut batch = Vec::with_capacity(STREAMING_BATCH_SIZE);
+        while let Some(pair) = seq.next_element::<Pair>()? {
+            keys.push(pair.key());
+            batch.push(pair);
+            if batch.len() == STREAMING_BATCH_SIZE {
+                self.table.put_pairs(&batch).map_err(de::Error::custom)?;
+                batch.clear();
+            }
+        }
+        self.table.put_pairs(&batch).map_err(de::Error::custom)?;
+        Ok(keys)
+    }
 }
 
 // @TODO should SourceChain have a bound on HashTable for consistency?
@@ -128,12 +1169,19 @@ pub trait SourceChain {
     fn top_pair(&self) -> Option<Pair>;
     /// get the top Pair by Entry type
     fn top_pair_type(&self, t: &str) -> Option<Pair>;
+    /// get the top Pair with any of the given Entry types
+    fn top_pair_of_types(&self, types: &[&str]) -> Option<Pair>;
 
     /// push a new Entry on to the top of the Chain
     /// the Pair for the new Entry is automatically generated and validated against the current top
     /// Pair to ensure the chain links up correctly across the underlying table data
     /// the newly created and pushed Pair is returned in the fn Result
     fn push_entry(&mut self, entry: &Entry) -> Result<Pair, HolochainError>;
+    /// push_entry() unless an entry with the same hash and type is already in the chain
+    /// returns Ok(None) without pushing anything for an entry that is already present
+    fn push_entry_if_absent(&mut self, entry: &Entry) -> Result<Option<Pair>, HolochainError>;
+    /// push_entry() for an Entry built from an entry type and serialized JSON content
+    fn push_json(&mut self, entry_type: &str, json: &str) -> Result<Pair, HolochainError>;
     /// get an Entry by Entry key from the HashTable if it exists
     fn entry(&self, entry_hash: &str) -> Result<Option<Pair>, HolochainError>;
 
@@ -141,6 +1189,13 @@ pub trait SourceChain {
     fn push_pair(&mut self, pair: &Pair) -> Result<Pair, HolochainError>;
     /// get a Pair by Pair/Header key from the HashTable if it exists
     fn pair(&self, message: &str) -> Result<Option<Pair>, HolochainError>;
+
+    /// call f for every Pair in the chain, oldest first
+    /// intended for rebuilding derived state (e.g. indexes) from an existing chain
+    fn replay<F: FnMut(&Pair)>(&self, f: F);
+
+    /// count the entries in the chain by entry type, from their headers alone
+    fn entry_count_by_type(&self) -> HashMap<String, usize>;
 }
 
 impl SourceChain for Chain {
@@ -156,6 +1211,14 @@ impl SourceChain for Chain {
         self.iter().find(|p| p.header().entry_type() == t)
     }
 
+    fn top_pair_of_types(&self, types: &[&str]) -> Option<Pair> {
+        if types.is_empty() {
+            return None;
+        }
+        self.iter()
+            .find(|p| types.contains(&p.header().entry_type()))
+    }
+
     fn push_pair(&mut self, pair: &Pair) -> Result<Pair, HolochainError> {
         if !(pair.validate()) {
             return Err(HolochainError::new(
@@ -163,36 +1226,76 @@ impl SourceChain for Chain {
             ));
         }
 
-        let top_pair = self.top_pair().as_ref().map(|p| p.key());
-        let next_pair = pair.header().link();
-
-        if top_pair != next_pair {
-            return Err(HolochainError::new(&format!(
-                "top pair did not match next hash pair from pushed pair: {:?} vs. {:?}",
-                top_pair, next_pair,
-            )));
+        let mut table = match self.sys_table {
+            Some(ref sys_table) if pair.entry().is_system_type() => sys_table.clone(),
+            _ => self.table(),
+        };
+        if let Some(mut sys_table) = self.sys_table() {
+            if !pair.entry().is_system_type() {
+                sys_table
+                    .put_pair(&pair.header_only())
+                    .map_err(|e| e.with_context("while putting header"))?;
+            }
         }
+        table
+            .put_pair(pair)
+            .map_err(|e| e.with_context("while putting pair"))?;
 
-        self.table.put_pair(&pair.clone())?;
-
-        // @TODO instead of unwrapping this, move all the above validation logic inside of
-        // set_top_pair()
-        // @see https://github.com/holochain/holochain-rust/issues/258
+        // set_top_pair() rejects pairs that don't link to the current top pair
         // @TODO if top pair set fails but commit succeeds?
         // @see https://github.com/holochain/holochain-rust/issues/259
-        self.set_top_pair(&Some(pair.clone()))?;
+        self.set_top_pair(&Some(pair.clone()))
+            .map_err(|e| e.with_context("while setting top pair"))?;
 
         Ok(pair.clone())
     }
 
     fn push_entry(&mut self, entry: &Entry) -> Result<Pair, HolochainError> {
-        let pair = Pair::new(self, entry);
+        if let Some(max_entry_size) = self.max_entry_size {
+            let entry_size = entry.content().len();
+            if entry_size > max_entry_size {
+                return Err(HolochainError::ValidationError {
+                    field: Some("content".to_string()),
+                    message: format!(
+                        "content of {} entry is {} bytes, over the limit of {}",
+                        entry.entry_type(),
+                        entry_size,
+                        max_entry_size
+                    ),
+                });
+            }
+        }
+        if self.json_entry_types.contains(&entry.entry_type()) && !entry.is_valid_json() {
+            return Err(HolochainError::ValidationError {
+                field: Some("content".to_string()),
+                message: format!("content of {} entry is not valid JSON", entry.entry_type()),
+            });
+        }
+        if let Some(validator) = self.entry_validators.get(&entry.entry_type()) {
+            validator.validate(entry, self)?;
+        }
+
+        let entry = entry.with_hash_algorithm(self.hash_algorithm);
+        let pair = Pair::new(&Header::new(self, &entry), &entry)?;
         self.push_pair(&pair)
     }
 
+    fn push_json(&mut self, entry_type: &str, json: &str) -> Result<Pair, HolochainError> {
+        self.push_entry(&Entry::new(entry_type, json))
+    }
+
+    fn push_entry_if_absent(&mut self, entry: &Entry) -> Result<Option<Pair>, HolochainError> {
+        let entry_hash = entry.with_hash_algorithm(self.hash_algorithm).hash();
+        if self.iter().any(|p| {
+            p.entry_address() == entry_hash && p.entry().entry_type() == entry.entry_type()
+        }) {
+            return Ok(None);
+        }
+        self.push_entry(entry).map(Some)
+    }
+
     fn pair(&self, k: &str) -> Result<Option<Pair>, HolochainError> {
-        let response = self.table.block_on_ask(Protocol::GetPair(k.to_string()));
-        unwrap_to!(response => Protocol::GetPairResult).clone()
+        find_pair(&self.table, &self.sys_table, k)
     }
 
     fn entry(&self, entry_hash: &str) -> Result<Option<Pair>, HolochainError> {
@@ -202,7 +1305,23 @@ impl SourceChain for Chain {
                 .iter()
                 // @TODO entry hashes are NOT unique across pairs so k/v lookups can't be 1:1
                 // @see https://github.com/holochain/holochain-rust/issues/145
-                .find(|p| p.entry().hash() == entry_hash))
+                .find(|p| p.entry_address() == entry_hash))
+    }
+
+    fn replay<F: FnMut(&Pair)>(&self, mut f: F) {
+        let mut pairs = self.iter().collect::<Vec<Pair>>();
+        pairs.reverse();
+        for p in pairs {
+            f(&p);
+        }
+    }
+
+    fn entry_count_by_type(&self) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for h in self.headers() {
+            *counts.entry(h.entry_type().to_string()).or_insert(0) += 1;
+        }
+        counts
     }
 }
 
@@ -219,23 +1338,86 @@ impl ToJson for Chain {
 #[cfg(test)]
 pub mod tests {
 
-    use super::Chain;
-    use chain::SourceChain;
+    use super::{Chain, STREAMING_BATCH_SIZE};
+    use agent::keys::tests::test_keys;
+    use chain::{header::Header, SourceChain};
+    use chrono::{DateTime, Utc};
+    use error::HolochainError;
+    use hash::HashAlgorithm;
     use hash_table::{
-        actor::tests::test_table_actor,
-        entry::tests::{test_entry, test_entry_a, test_entry_b, test_type_a, test_type_b},
-        pair::Pair,
-        HashTable,
+        actor::{tests::test_table_actor, HashTableActor},
+        entry::{
+            tests::{
+                test_entry, test_entry_a, test_entry_b, test_entry_unique, test_type_a, test_type_b,
+            },
+            Entry,
+        },
+        memory::MemTable,
+        pair::{
+            tests::{test_pair_unique, test_pair_with_timestamp},
+            Pair,
+        },
+        pair_meta::PairMeta,
+        sys_entry::EntryType,
+        HashTable, TableStats,
     };
-    use json::ToJson;
+    use holochain_dna::zome::entry_types::Sharing;
+    use json::{FromJson, ToJson};
     use key::Key;
-    use std::thread;
+    use serde_json;
+    use std::{
+        collections::HashMap,
+        io::Cursor,
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
 
     /// builds a dummy chain for testing
     pub fn test_chain() -> Chain {
         Chain::new(test_table_actor())
     }
 
+    /// fixed time for the clock of deterministic test chains
+    pub fn test_time() -> DateTime<Utc> {
+        "2018-01-01T00:00:00Z"
+            .parse()
+            .expect("a literal RFC3339 time should parse")
+    }
+
+    /// dummy deterministic chain with a fixed clock and the test keys over a new table, with the
+    /// given entries pushed in order
+    /// pushing the same entries always builds a chain with byte-identical JSON
+    pub fn test_deterministic_chain(entries: &[Entry]) -> Chain {
+        let mut chain = Chain::deterministic(test_table_actor(), test_time, test_keys());
+        for entry in entries {
+            chain
+                .push_entry(entry)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+        chain
+    }
+
+    #[test]
+    /// test that pushing the same entries to deterministic chains gives the same JSON
+    fn deterministic_chain() {
+        let entries = vec![test_entry_a(), test_entry_b(), test_entry_a()];
+
+        let chain_a = test_deterministic_chain(&entries);
+        let chain_b = test_deterministic_chain(&entries);
+
+        assert_eq!(
+            chain_a.to_json().expect("a chain should serialize"),
+            chain_b.to_json().expect("a chain should serialize"),
+        );
+        assert_eq!(Some(test_keys()), chain_a.keys());
+        for p in chain_a.iter() {
+            assert_eq!(test_time().to_rfc3339(), p.header().timestamp());
+        }
+    }
+
     #[test]
     /// smoke test for new chains
     fn new() {
@@ -269,6 +1451,149 @@ pub mod tests {
         assert_ne!(chain2, chain3);
     }
 
+    /// MemTable that counts the Pairs, Headers and batches of Pairs looked up in it
+    /// clones share the counters
+    #[derive(Clone)]
+    struct CountingTable {
+        table: MemTable,
+        lookups: Arc<AtomicUsize>,
+        header_lookups: Arc<AtomicUsize>,
+        batch_lookups: Arc<AtomicUsize>,
+    }
+
+    impl CountingTable {
+        fn new() -> CountingTable {
+            CountingTable {
+                table: MemTable::new(),
+                lookups: Arc::new(AtomicUsize::new(0)),
+                header_lookups: Arc::new(AtomicUsize::new(0)),
+                batch_lookups: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+
+        /// sets every counter back to 0
+        fn reset(&self) {
+            self.lookups.store(0, Ordering::SeqCst);
+            self.header_lookups.store(0, Ordering::SeqCst);
+            self.batch_lookups.store(0, Ordering::SeqCst);
+        }
+    }
+
+    impl HashTable for CountingTable {
+        fn put_pair(&mut self, pair: &Pair) -> Result<(), HolochainError> {
+            self.table.put_pair(pair)
+        }
+
+        fn pair(&self, key: &str) -> Result<Option<Pair>, HolochainError> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            self.table.pair(key)
+        }
+
+        fn pairs(&self, keys: &[String]) -> Result<Vec<Option<Pair>>, HolochainError> {
+            self.batch_lookups.fetch_add(1, Ordering::SeqCst);
+            self.table.pairs(keys)
+        }
+
+        fn header(&self, key: &str) -> Result<Option<Header>, HolochainError> {
+            self.header_lookups.fetch_add(1, Ordering::SeqCst);
+            self.table.header(key)
+        }
+
+        fn assert_pair_meta(&mut self, meta: &PairMeta) -> Result<(), HolochainError> {
+            self.table.assert_pair_meta(meta)
+        }
+
+        fn pair_meta(&mut self, key: &str) -> Result<Option<PairMeta>, HolochainError> {
+            self.table.pair_meta(key)
+        }
+
+        fn metas_for_pair(&mut self, pair: &Pair) -> Result<Vec<PairMeta>, HolochainError> {
+            self.table.metas_for_pair(pair)
+        }
+
+        fn stats(&self) -> Result<TableStats, HolochainError> {
+            self.table.stats()
+        }
+    }
+
+    /// chain over a CountingTable with n unique entries pushed
+    /// returns the chain and a clone of its table with the counters reset
+    fn test_counting_chain(n: usize) -> (Chain, CountingTable) {
+        let table = CountingTable::new();
+        let mut chain = Chain::new(HashTableActor::new_ref(table.clone()));
+        for _ in 0..n {
+            chain
+                .push_entry(&test_entry_unique())
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+        table.reset();
+        (chain, table)
+    }
+
+    #[test]
+    /// test that equality of chains over the same table only compares the tops
+    fn eq_without_walking() {
+        let table = CountingTable::new();
+        let table_actor = HashTableActor::new_ref(table.clone());
+        let mut chains = Vec::new();
+        for _ in 0..2 {
+            let mut chain = Chain::new(table_actor.clone());
+            for i in 0..100 {
+                chain
+                    .push_entry(&Entry::new(&test_type_a(), &i.to_string()))
+                    .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+            }
+            chains.push(chain);
+        }
+
+        table.reset();
+        assert_eq!(chains[0], chains[1]);
+        assert_eq!(0, table.lookups.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    /// test that chains with the same top over different tables are validated and compared in full
+    fn eq_across_tables() {
+        let mut chain = test_chain();
+        let p1 = chain
+            .push_entry(&test_entry_a())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let p2 = chain
+            .push_entry(&test_entry_b())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        let mut copy = Chain::new(test_table_actor());
+        for p in vec![p1.clone(), p2.clone()] {
+            copy.push_pair(&p)
+                .expect("pushing a valid pair to an exlusively owned chain shouldn't fail");
+        }
+        assert_eq!(chain, copy);
+
+        // a table holding tampered content under the key of the first pair
+        let json = p1
+            .to_json()
+            .expect("pair should serialize")
+            .replace(&test_entry_a().content(), "tampered content");
+        let tampered = Pair::from_json(&json).expect("tampered pair should deserialize");
+        let mut table = MemTable::new();
+        table
+            .put_pair(&tampered)
+            .expect("putting a pair in a table shouldn't fail");
+        table
+            .put_pair(&p2)
+            .expect("putting a pair in a table shouldn't fail");
+        let tampered_chain = Chain::new(HashTableActor::new_ref(table));
+        tampered_chain
+            .set_top_pair(&Some(tampered))
+            .expect("setting the genesis pair shouldn't fail");
+        tampered_chain
+            .set_top_pair(&Some(p2))
+            .expect("setting a linked top pair shouldn't fail");
+
+        assert_eq!(chain.top_pair(), tampered_chain.top_pair());
+        assert_ne!(chain, tampered_chain);
+    }
+
     #[test]
     /// tests for chain.top_pair()
     fn top_pair() {
@@ -329,6 +1654,134 @@ pub mod tests {
         assert_eq!(table_pair, chain_pair);
     }
 
+    #[test]
+    /// test that system entries and headers go to the system table and app entries to the table
+    fn sys_table_push() {
+        let table_actor = test_table_actor();
+        let sys_table_actor = test_table_actor();
+        let mut chain = Chain::new(table_actor.clone()).with_sys_table(sys_table_actor.clone());
+
+        let sys_pair = chain
+            .push_entry(&Entry::new(EntryType::Dna.as_str(), "{}"))
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let app_pair = chain
+            .push_entry(&test_entry())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        assert_eq!(
+            Some(sys_pair.clone()),
+            sys_table_actor.pair(&sys_pair.key()).unwrap()
+        );
+        assert_eq!(None, table_actor.pair(&sys_pair.key()).unwrap());
+        assert_eq!(
+            Some(app_pair.clone()),
+            table_actor.pair(&app_pair.key()).unwrap()
+        );
+        // the header of the app entry lands in the system table without the content
+        assert_eq!(
+            Some(app_pair.header().clone()),
+            sys_table_actor.header(&app_pair.key()).unwrap()
+        );
+        assert_eq!(
+            Some(app_pair.header_only()),
+            sys_table_actor.pair(&app_pair.key()).unwrap()
+        );
+
+        // lookups and iteration see both tables
+        assert_eq!(Some(sys_pair.clone()), chain.pair(&sys_pair.key()).unwrap());
+        assert_eq!(
+            Some(sys_pair.clone()),
+            chain.entry(&sys_pair.entry_address()).unwrap()
+        );
+        assert_eq!(
+            vec![app_pair, sys_pair],
+            chain.iter().collect::<Vec<Pair>>()
+        );
+        assert_eq!(Ok(()), chain.verify_hashes());
+    }
+
+    #[test]
+    /// test chain.genesis_hash()
+    fn genesis_hash() {
+        let mut chain = test_chain();
+        assert_eq!(None, chain.genesis_hash());
+
+        let genesis = chain
+            .push_entry(&test_entry_unique())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        for _ in 0..4 {
+            chain
+                .push_entry(&test_entry_unique())
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+        assert_eq!(5, chain.iter().count());
+
+        assert_eq!(Some(genesis.key()), chain.genesis_hash());
+        assert_eq!(Some(genesis.key()), chain.clone().genesis_hash());
+
+        chain.clear().expect("clearing a chain shouldn't fail");
+        assert_eq!(None, chain.genesis_hash());
+
+        let genesis = chain
+            .push_entry(&test_entry_unique())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(Some(genesis.key()), chain.genesis_hash());
+    }
+
+    #[test]
+    /// test chain.clear()
+    fn clear() {
+        let table_actor = test_table_actor();
+        let mut chain = Chain::new(table_actor.clone());
+        let pair = chain
+            .push_entry(&test_entry_a())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        chain
+            .push_entry(&test_entry_b())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        assert_eq!(Ok(()), chain.clear());
+        assert_eq!(None, chain.top_pair());
+        assert_eq!(0, chain.iter().count());
+        assert_eq!(Some(pair.clone()), table_actor.pair(&pair.key()).unwrap());
+
+        // the chain can be built up again from scratch
+        assert_eq!(Some(pair), chain.push_entry(&test_entry_a()).ok());
+    }
+
+    #[test]
+    /// test that push errors say which step failed
+    fn push_error_context() {
+        use hash_table::file::tests::test_table;
+        use std::{error::Error, fs, path::MAIN_SEPARATOR};
+
+        let (table, dir) = test_table();
+        let mut chain = Chain::new(HashTableActor::new_ref(table));
+
+        // a file where the pairs directory should be makes every put fail
+        fs::write(
+            format!("{}{}pairs", dir.path().to_str().unwrap(), MAIN_SEPARATOR),
+            "",
+        ).expect("test should be able to write to its temp dir");
+
+        let err = chain
+            .push_entry(&test_entry())
+            .expect_err("pushing to a broken table should fail");
+        assert!(err.description().starts_with("while putting pair: "));
+        assert_eq!("IoError", err.variant_name());
+        assert_eq!(None, chain.top_pair());
+
+        // a pair that doesn't link to the top pair fails on the top pair instead
+        let mut chain = test_chain();
+        chain
+            .push_entry(&test_entry_a())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let err = chain
+            .push_pair(&test_pair_unique())
+            .expect_err("pushing an unlinked pair should fail");
+        assert!(err.description().starts_with("while setting top pair: "));
+    }
+
     #[test]
     /// tests for chain.push()
     fn push() {
@@ -358,137 +1811,656 @@ pub mod tests {
     }
 
     #[test]
-    /// test chain.validate()
-    fn validate() {
-        let mut chain = test_chain();
+    /// test that entries of JSON types must have JSON content
+    fn push_json_entry() {
+        let mut chain = test_chain().with_json_entry_types(&["jsonType"]);
 
-        let e1 = test_entry_a();
-        let e2 = test_entry_b();
+        assert!(chain
+            .push_entry(&Entry::new("jsonType", r#"{"foo":"bar"}"#))
+            .is_ok());
+        assert_eq!(
+            Err(HolochainError::ValidationError {
+                field: Some("content".to_string()),
+                message: "content of jsonType entry is not valid JSON".to_string(),
+            }),
+            chain.push_entry(&Entry::new("jsonType", "{foo")),
+        );
+        assert_eq!(1, chain.iter().count());
 
-        assert!(chain.validate());
+        // other types aren't checked
+        assert!(chain.push_entry(&Entry::new("fooType", "{foo")).is_ok());
+    }
 
-        chain
-            .push_entry(&e1)
-            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
-        assert!(chain.validate());
+    #[test]
+    /// test that entries over the size limit are rejected
+    fn push_entry_max_size() {
+        let mut chain = test_chain().with_max_entry_size(3);
 
-        chain
-            .push_entry(&e2)
-            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
-        assert!(chain.validate());
+        assert!(chain.push_entry(&Entry::new("fooType", "foo")).is_ok());
+        assert_eq!(
+            Err(HolochainError::ValidationError {
+                field: Some("content".to_string()),
+                message: "content of fooType entry is 4 bytes, over the limit of 3".to_string(),
+            }),
+            chain.push_entry(&Entry::new("fooType", "food")),
+        );
+        assert_eq!(1, chain.iter().count());
     }
 
     #[test]
-    /// test chain.push() and chain.get() together
-    fn round_trip() {
+    /// test that registered validators run for their entry type only
+    fn push_entry_validator() {
+        let not_empty = |entry: &Entry, _chain: &Chain| {
+            if entry.content().is_empty() {
+                Err(HolochainError::new("content is empty"))
+            } else {
+                Ok(())
+            }
+        };
+        let mut chain = test_chain().with_entry_validator(&test_type_a(), not_empty);
+
+        assert_eq!(
+            Err(HolochainError::new("content is empty")),
+            chain.push_entry(&Entry::new(&test_type_a(), "")),
+        );
+        assert_eq!(None, chain.top_pair());
+
+        assert!(chain.push_entry(&Entry::new(&test_type_a(), "foo")).is_ok());
+        assert!(chain.push_entry(&Entry::new(&test_type_b(), "")).is_ok());
+        assert_eq!(2, chain.iter().count());
+    }
+
+    #[test]
+    /// test chain.push_json()
+    fn push_json() {
+        let mut chain = test_chain().with_json_entry_types(&["jsonType"]);
+        let json = r#"{"foo":"bar"}"#;
+
+        let pair = chain
+            .push_json("jsonType", json)
+            .expect("pushing valid JSON to an exlusively owned chain shouldn't fail");
+        assert_eq!(json, pair.entry().content());
+        assert_eq!(
+            Some(pair.clone()),
+            chain.entry(&pair.entry().hash()).unwrap()
+        );
+
+        assert!(chain.push_json("jsonType", "{foo").is_err());
+    }
+
+    #[test]
+    /// test that entry metadata is stored without changing the entry address
+    fn push_entry_with_metadata() {
         let mut chain = test_chain();
-        let entry = test_entry();
+        let metadata = serde_json::Value::String("read".to_string());
+        let entry = test_entry().with_metadata(metadata.clone());
+
         let pair = chain
             .push_entry(&entry)
             .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(test_entry().hash(), pair.entry_address());
 
-        assert_eq!(
-            Some(&pair),
-            chain
-                .pair(&pair.key())
-                .expect("getting an entry from a chain shouldn't fail")
-                .as_ref()
-        );
+        let stored = chain
+            .entry(&test_entry().hash())
+            .unwrap()
+            .expect("pushed entry should be in the chain");
+        assert_eq!(Some(metadata), stored.entry().metadata());
     }
 
     #[test]
-    /// show that we can push the chain a bit without issues e.g. async
-    fn round_trip_stress_test() {
-        let h = thread::spawn(|| {
-            let mut chain = test_chain();
-            let entry = test_entry();
+    /// test that metadata can be attached to an entry that is already in the chain
+    fn set_entry_metadata() {
+        let mut chain = test_chain();
+        let pair = chain
+            .push_entry(&test_entry())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(None, pair.entry().metadata());
+
+        let metadata = serde_json::Value::String("read".to_string());
+        let annotated = chain
+            .set_entry_metadata(&pair.key(), metadata.clone())
+            .expect("setting metadata on a pushed entry shouldn't fail");
+        assert_eq!(pair.key(), annotated.key());
+
+        let stored = chain
+            .entry(&test_entry().hash())
+            .unwrap()
+            .expect("pushed entry should be in the chain");
+        assert_eq!(test_entry().hash(), stored.entry_address());
+        assert_eq!(Some(metadata.clone()), stored.entry().metadata());
+        assert_eq!(
+            Some(metadata),
+            chain.top_pair().and_then(|p| p.entry().metadata())
+        );
+        assert_eq!(Ok(()), chain.verify_hashes());
 
-            for _ in 1..100 {
-                let pair = chain.push_entry(&entry).unwrap();
-                assert_eq!(Some(pair.clone()), chain.pair(&pair.key()).unwrap(),);
-            }
-        });
-        h.join().unwrap();
+        assert_eq!(
+            Err(HolochainError::new("pair foo is not in the table")),
+            chain.set_entry_metadata("foo", serde_json::Value::Null)
+        );
     }
 
     #[test]
-    /// test chain.iter()
-    fn iter() {
+    /// test chain.subscribe_commits()
+    fn subscribe_commits() {
         let mut chain = test_chain();
-
-        let e1 = test_entry_a();
-        let e2 = test_entry_b();
+        let receiver = chain.subscribe_commits();
 
         let p1 = chain
-            .push_entry(&e1)
+            .push_entry(&test_entry_a())
             .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
         let p2 = chain
-            .push_entry(&e2)
+            .push_entry(&test_entry_b())
             .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
 
-        assert_eq!(vec![p2, p1], chain.iter().collect::<Vec<Pair>>());
+        assert_eq!(Ok(p1), receiver.try_recv());
+        assert_eq!(Ok(p2), receiver.try_recv());
+        assert!(receiver.try_recv().is_err());
+
+        // pushing still works once the only subscriber is gone
+        drop(receiver);
+        assert!(chain.push_entry(&test_entry_unique()).is_ok());
     }
 
     #[test]
-    /// test chain.iter() functional interface
-    fn iter_functional() {
-        let mut chain = test_chain();
-
-        let e1 = test_entry_a();
-        let e2 = test_entry_b();
+    /// test pushing to a chain with a non-default hash algorithm
+    fn push_entry_hash_algorithm() {
+        let mut chain = test_chain().with_hash_algorithm(HashAlgorithm::Sha256Hex);
+        let is_hex = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_digit(16));
 
         let p1 = chain
-            .push_entry(&e1)
+            .push_entry(&test_entry_a())
             .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
-        let _p2 = chain
-            .push_entry(&e2)
+        let p2 = chain
+            .push_entry(&test_entry_b())
             .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
-        let p3 = chain
-            .push_entry(&e1)
+
+        for p in vec![&p1, &p2] {
+            assert!(is_hex(&p.entry_address()));
+            assert!(is_hex(&p.header_address()));
+        }
+        assert_eq!(Some(p1.key()), p2.header().link());
+        assert_eq!(Some(p1.clone()), chain.entry(&p1.entry_address()).unwrap());
+        assert!(chain.validate());
+        assert_eq!(Ok(()), chain.verify_hashes());
+        assert_eq!(None, chain.push_entry_if_absent(&test_entry_a()).unwrap());
+    }
+
+    #[test]
+    /// test chain.push_entry_and_get()
+    fn push_entry_and_get() {
+        let mut chain = test_chain();
+        let entry = test_entry();
+
+        let (pair, stored) = chain
+            .push_entry_and_get(&entry)
             .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(entry, stored);
+        assert_eq!(entry.content(), stored.content());
+        assert_eq!(Some(pair), chain.top_pair());
+    }
 
+    #[test]
+    /// test chain.push_entry_if_absent()
+    fn push_entry_if_absent() {
+        let mut chain = test_chain();
+
+        let p1 = chain
+            .push_entry_if_absent(&test_entry_a())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(chain.top_pair(), p1);
         assert_eq!(
-            vec![p3, p1],
+            None,
             chain
-                .iter()
-                .filter(|p| p.entry().entry_type() == "testEntryType")
-                .collect::<Vec<Pair>>()
+                .push_entry_if_absent(&test_entry_a())
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail")
         );
+        assert_eq!(1, chain.iter().count());
+
+        // same content with a different type is a different entry
+        let e2 = Entry::new(&test_type_b(), &test_entry_a().content());
+        assert!(chain
+            .push_entry_if_absent(&e2)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail")
+            .is_some());
+        assert_eq!(2, chain.iter().count());
     }
 
     #[test]
-    /// test chain.get()
-    fn get() {
-        let mut chain = test_chain();
+    /// test chain.push_entry_with_retry() with several writers sharing one chain
+    fn push_entry_with_retry() {
+        let chain = test_chain();
+
+        let writers: Vec<_> = (0..4)
+            .map(|_| {
+                let mut chain = chain.clone();
+                thread::spawn(move || {
+                    for _ in 0..10 {
+                        chain
+                            .push_entry_with_retry(&test_entry_unique(), 100)
+                            .expect("retrying a push should get past concurrent pushes");
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
 
-        let e1 = test_entry_a();
-        let e2 = test_entry_b();
-        let e3 = test_entry_a();
+        assert_eq!(40, chain.iter().count());
+        assert!(chain.validate());
+        assert_eq!(Ok(()), chain.verify_hashes());
+    }
+
+    #[test]
+    /// test chain.validate_with()
+    fn validate_with() {
+        let link_in_table = |p: &Pair, chain: &Chain| match p.header().link() {
+            Some(link) => match chain.pair(&link)? {
+                Some(_) => Ok(()),
+                None => Err(HolochainError::new(&format!("missing link {}", link))),
+            },
+            None => Ok(()),
+        };
+
+        let mut chain = test_chain();
+        assert_eq!(Ok(()), chain.validate_with(link_in_table));
 
         let p1 = chain
-            .push_entry(&e1)
+            .push_entry(&test_entry_a())
             .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
         let p2 = chain
-            .push_entry(&e2)
-            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
-        let p3 = chain
-            .push_entry(&e3)
+            .push_entry(&test_entry_b())
             .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(Ok(()), chain.validate_with(link_in_table));
+
+        // a chain topped by p2 over a table that is missing p1
+        let broken = test_chain();
+        broken
+            .table()
+            .put_pair(&p2)
+            .expect("putting a pair in a table shouldn't fail");
+        broken
+            .set_top_pair(&Some(p1.clone()))
+            .expect("setting a genesis pair as top shouldn't fail");
+        broken
+            .set_top_pair(&Some(p2))
+            .expect("setting a pair linked to the top shouldn't fail");
 
         assert_eq!(
-            None,
-            chain
-                .pair("")
-                .expect("getting an entry from a chain shouldn't fail")
-        );
-        assert_eq!(
-            Some(&p1),
-            chain
-                .pair(&p1.key())
-                .expect("getting an entry from a chain shouldn't fail")
-                .as_ref()
+            Err(HolochainError::new(&format!("missing link {}", p1.key()))),
+            broken.validate_with(link_in_table)
         );
-        assert_eq!(
+    }
+
+    #[test]
+    /// test that chain.to_public_json() leaves out private pairs
+    fn to_public_json() {
+        let mut chain = test_chain();
+        let public_pair = chain
+            .push_entry(&test_entry_a())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let private_pair = chain
+            .push_entry(&test_entry_b().with_sharing(Sharing::Private))
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        let public: Vec<Pair> = serde_json::from_str(&chain.to_public_json().unwrap()).unwrap();
+        assert_eq!(vec![public_pair.clone()], public);
+
+        let all: Vec<Pair> = serde_json::from_str(&chain.to_json().unwrap()).unwrap();
+        assert_eq!(vec![private_pair, public_pair], all);
+    }
+
+    #[test]
+    /// test chain.replay_validation()
+    fn replay_validation() {
+        let mut chain = test_chain();
+        let rejected = Entry::new(&test_type_a(), "rejected");
+        for e in vec![test_entry_a(), rejected.clone(), test_entry_b()] {
+            chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+        let top_pair = chain.top_pair();
+
+        let failures = chain.replay_validation(|e| {
+            if e.content() == "rejected" {
+                Err(HolochainError::new("rejected content"))
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(
+            vec![(rejected.hash(), HolochainError::new("rejected content"))],
+            failures
+        );
+        assert_eq!(top_pair, chain.top_pair());
+
+        assert!(chain.replay_validation(|_| Ok(())).is_empty());
+    }
+
+    #[test]
+    /// test chain.validate_no_orphan_links()
+    fn validate_no_orphan_links() {
+        let mut chain = test_chain();
+        assert_eq!(Ok(()), chain.validate_no_orphan_links());
+
+        let p1 = chain
+            .push_entry(&test_entry_a())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let p2 = chain
+            .push_entry(&test_entry_a())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(Ok(()), chain.validate_no_orphan_links());
+
+        // import only p2 so that both of its links dangle
+        let partial = test_chain();
+        partial
+            .table()
+            .put_pair(&p2)
+            .expect("putting a pair in a table shouldn't fail");
+        partial
+            .set_top_pair(&Some(p1.clone()))
+            .expect("setting a genesis pair as top shouldn't fail");
+        partial
+            .set_top_pair(&Some(p2.clone()))
+            .expect("setting a pair linked to the top shouldn't fail");
+
+        assert_eq!(
+            Err(HolochainError::new(&format!(
+                "pair {} links to missing pair {}",
+                p2.key(),
+                p1.key()
+            ))),
+            partial.validate_no_orphan_links()
+        );
+    }
+
+    #[test]
+    /// test chain.validate_genesis_is_dna()
+    fn validate_genesis_is_dna() {
+        let mut chain = test_chain();
+        assert_eq!(
+            Err(HolochainError::new(
+                "the chain is empty so has no DNA genesis pair"
+            )),
+            chain.validate_genesis_is_dna()
+        );
+
+        for e in vec![Entry::new(EntryType::Dna.as_str(), "{}"), test_entry_a()] {
+            chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+        assert_eq!(Ok(()), chain.validate_genesis_is_dna());
+
+        let mut app_first = test_chain();
+        let genesis = app_first
+            .push_entry(&test_entry_a())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        app_first
+            .push_entry(&Entry::new(EntryType::Dna.as_str(), "{}"))
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(
+            Err(HolochainError::new(&format!(
+                "genesis pair {} has entry type {} instead of the DNA type",
+                genesis.key(),
+                test_type_a()
+            ))),
+            app_first.validate_genesis_is_dna()
+        );
+    }
+
+    #[test]
+    /// test chain.validate_entry_types()
+    fn validate_entry_types() {
+        let mut chain = test_chain();
+        let allowed = vec![test_type_a()];
+        assert_eq!(Ok(()), chain.validate_entry_types(&allowed));
+
+        for e in vec![Entry::new(EntryType::Dna.as_str(), "{}"), test_entry_a()] {
+            chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+        assert_eq!(Ok(()), chain.validate_entry_types(&allowed));
+
+        let undeclared = chain
+            .push_entry(&test_entry_b())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(
+            Err(HolochainError::ValidationError {
+                field: Some("entry_type".to_string()),
+                message: format!(
+                    "pair {} has undeclared entry type {}",
+                    undeclared.key(),
+                    test_type_b()
+                ),
+            }),
+            chain.validate_entry_types(&allowed)
+        );
+    }
+
+    #[test]
+    /// test chain.verify_hashes()
+    fn verify_hashes() {
+        let mut chain = test_chain();
+        assert_eq!(Ok(()), chain.verify_hashes());
+
+        let p1 = chain
+            .push_entry(&test_entry_a())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let p2 = chain
+            .push_entry(&test_entry_b())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(Ok(()), chain.verify_hashes());
+
+        // change the stored content of the first entry without touching its header
+        let json = p1
+            .to_json()
+            .expect("pair should serialize")
+            .replace(&test_entry_a().content(), "tampered content");
+        let tampered = Pair::from_json(&json).expect("tampered pair should deserialize");
+
+        // table actors reject a different pair under an existing key as a hash collision, so the
+        // tampered pair is written to the backing table before the actor wraps it
+        let mut table = MemTable::new();
+        table
+            .put_pair(&tampered)
+            .expect("putting a pair in a table shouldn't fail");
+        table
+            .put_pair(&p2)
+            .expect("putting a pair in a table shouldn't fail");
+        let chain = Chain::new(HashTableActor::new_ref(table));
+        chain
+            .set_top_pair(&Some(tampered.clone()))
+            .expect("setting the genesis pair shouldn't fail");
+        chain
+            .set_top_pair(&Some(p2))
+            .expect("setting a linked top pair shouldn't fail");
+
+        assert_eq!(
+            Err(HolochainError::new(&format!(
+                "entry hash mismatch for pair {}: header has {} but entry hashes to {}",
+                p1.key(),
+                test_entry_a().hash(),
+                tampered.entry().hash(),
+            ))),
+            chain.verify_hashes()
+        );
+    }
+
+    #[test]
+    /// test chain.validate()
+    fn validate() {
+        let mut chain = test_chain();
+
+        let e1 = test_entry_a();
+        let e2 = test_entry_b();
+
+        assert!(chain.validate());
+
+        chain
+            .push_entry(&e1)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert!(chain.validate());
+
+        chain
+            .push_entry(&e2)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert!(chain.validate());
+    }
+
+    #[test]
+    /// test chain.push() and chain.get() together
+    fn round_trip() {
+        let mut chain = test_chain();
+        let entry = test_entry();
+        let pair = chain
+            .push_entry(&entry)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        assert_eq!(
+            Some(&pair),
+            chain
+                .pair(&pair.key())
+                .expect("getting an entry from a chain shouldn't fail")
+                .as_ref()
+        );
+    }
+
+    #[test]
+    /// show that we can push the chain a bit without issues e.g. async
+    fn round_trip_stress_test() {
+        let h = thread::spawn(|| {
+            let mut chain = test_chain();
+            let entry = test_entry();
+
+            for _ in 1..100 {
+                let pair = chain.push_entry(&entry).unwrap();
+                assert_eq!(Some(pair.clone()), chain.pair(&pair.key()).unwrap(),);
+            }
+        });
+        h.join().unwrap();
+    }
+
+    #[test]
+    /// test that readers sharing a chain actor never see a top pair missing from the table
+    fn concurrent_readers() {
+        let mut chain = test_chain();
+        let done = Arc::new(AtomicBool::new(false));
+
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let chain = chain.clone();
+                let done = done.clone();
+                thread::spawn(move || {
+                    let mut seen = Vec::new();
+                    while !done.load(Ordering::SeqCst) {
+                        if let Some(top_pair) = chain.top_pair() {
+                            assert_eq!(
+                                Some(top_pair.clone()),
+                                chain.table().pair(&top_pair.key()).unwrap(),
+                            );
+                            seen.push(top_pair);
+                        }
+                    }
+                    seen
+                })
+            })
+            .collect();
+
+        let mut pushed = Vec::new();
+        for _ in 0..50 {
+            pushed.push(chain.push_entry(&test_entry_unique()).unwrap());
+        }
+        done.store(true, Ordering::SeqCst);
+
+        for reader in readers {
+            for top_pair in reader.join().unwrap() {
+                assert!(pushed.contains(&top_pair));
+            }
+        }
+    }
+
+    #[test]
+    /// test chain.iter()
+    fn iter() {
+        let mut chain = test_chain();
+
+        let e1 = test_entry_a();
+        let e2 = test_entry_b();
+
+        let p1 = chain
+            .push_entry(&e1)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let p2 = chain
+            .push_entry(&e2)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        assert_eq!(vec![p2, p1], chain.iter().collect::<Vec<Pair>>());
+    }
+
+    #[test]
+    /// test chain.iter() functional interface
+    fn iter_functional() {
+        let mut chain = test_chain();
+
+        let e1 = test_entry_a();
+        let e2 = test_entry_b();
+
+        let p1 = chain
+            .push_entry(&e1)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let _p2 = chain
+            .push_entry(&e2)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let p3 = chain
+            .push_entry(&e1)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        assert_eq!(
+            vec![p3, p1],
+            chain
+                .iter()
+                .filter(|p| p.entry().entry_type() == "testEntryType")
+                .collect::<Vec<Pair>>()
+        );
+    }
+
+    #[test]
+    /// test chain.get()
+    fn get() {
+        let mut chain = test_chain();
+
+        let e1 = test_entry_a();
+        let e2 = test_entry_b();
+        let e3 = test_entry_a();
+
+        let p1 = chain
+            .push_entry(&e1)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let p2 = chain
+            .push_entry(&e2)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let p3 = chain
+            .push_entry(&e3)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        assert_eq!(
+            None,
+            chain
+                .pair("")
+                .expect("getting an entry from a chain shouldn't fail")
+        );
+        assert_eq!(
+            Some(&p1),
+            chain
+                .pair(&p1.key())
+                .expect("getting an entry from a chain shouldn't fail")
+                .as_ref()
+        );
+        assert_eq!(
             Some(&p2),
             chain
                 .pair(&p2.key())
@@ -614,48 +2586,907 @@ pub mod tests {
     }
 
     #[test]
-    /// test IntoIterator implementation
-    fn into_iter() {
+    /// test chain.first_pair_of_type()
+    fn first_pair_of_type() {
         let mut chain = test_chain();
+        assert_eq!(None, chain.first_pair_of_type(&test_type_a()));
+
+        let mut pairs = Vec::new();
+        for e in vec![test_entry_b(), test_entry_a(), test_entry_a()] {
+            pairs.push(
+                chain
+                    .push_entry(&e)
+                    .expect("pushing a valid entry to an exlusively owned chain shouldn't fail"),
+            );
+        }
 
-        let e1 = test_entry_a();
-        let e2 = test_entry_b();
-        let e3 = test_entry_a();
+        assert_eq!(
+            Some(pairs[1].clone()),
+            chain.first_pair_of_type(&test_type_a())
+        );
+        assert_eq!(
+            Some(pairs[0].clone()),
+            chain.first_pair_of_type(&test_type_b())
+        );
+        assert_eq!(None, chain.first_pair_of_type("fooType"));
+    }
 
-        let p1 = chain
-            .push_entry(&e1)
-            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
-        let p2 = chain
-            .push_entry(&e2)
+    #[test]
+    /// test chain.top_pair_of_types()
+    fn top_pair_of_types() {
+        let mut chain = test_chain();
+        let a = test_type_a();
+        let b = test_type_b();
+
+        assert_eq!(None, chain.top_pair_of_types(&[&a, &b]));
+
+        let pair1 = chain
+            .push_entry(&test_entry_a())
             .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
-        let p3 = chain
-            .push_entry(&e3)
+        let pair2 = chain
+            .push_entry(&test_entry_b())
             .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
 
-        // into_iter() returns clones of pairs
-        assert_eq!(vec![p3, p2, p1], chain.into_iter().collect::<Vec<Pair>>());
+        assert_eq!(Some(&pair2), chain.top_pair_of_types(&[&a, &b]).as_ref());
+        assert_eq!(Some(&pair1), chain.top_pair_of_types(&[&a]).as_ref());
+        assert_eq!(None, chain.top_pair_of_types(&["fooType"]));
+        assert_eq!(None, chain.top_pair_of_types(&[]));
     }
 
     #[test]
-    /// test to_json() and from_json() implementation
-    fn json_round_trip() {
+    /// test chain.replay()
+    fn replay() {
         let mut chain = test_chain();
 
-        let e1 = test_entry_a();
-        let e2 = test_entry_b();
-        let e3 = test_entry_a();
+        let mut seen = Vec::new();
+        chain.replay(|p| seen.push(p.entry().entry_type()));
+        assert!(seen.is_empty());
 
-        chain
-            .push_entry(&e1)
-            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
-        chain
-            .push_entry(&e2)
-            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
-        chain
-            .push_entry(&e3)
-            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        for e in vec![test_entry_a(), test_entry_b(), test_entry_a()] {
+            chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
 
-        let expected_json = "[{\"header\":{\"entry_type\":\"testEntryType\",\"timestamp\":\"\",\"link\":\"QmPT5HXvyv54Dg36YSK1A2rYvoPCNWoqpLzzZnHnQBcU6x\",\"entry_hash\":\"QmbXSE38SN3SuJDmHKSSw5qWWegvU7oTxrLDRavWjyxMrT\",\"entry_signature\":\"\",\"link_same_type\":\"QmawqBCVVap9KdaakqEHF4JzUjjLhmR7DpM5jgJko8j1rA\"},\"entry\":{\"content\":\"test entry content\",\"entry_type\":\"testEntryType\"}},{\"header\":{\"entry_type\":\"testEntryTypeB\",\"timestamp\":\"\",\"link\":\"QmawqBCVVap9KdaakqEHF4JzUjjLhmR7DpM5jgJko8j1rA\",\"entry_hash\":\"QmPz5jKXsxq7gPVAbPwx5gD2TqHfqB8n25feX5YH18JXrT\",\"entry_signature\":\"\",\"link_same_type\":null},\"entry\":{\"content\":\"other test entry content\",\"entry_type\":\"testEntryTypeB\"}},{\"header\":{\"entry_type\":\"testEntryType\",\"timestamp\":\"\",\"link\":null,\"entry_hash\":\"QmbXSE38SN3SuJDmHKSSw5qWWegvU7oTxrLDRavWjyxMrT\",\"entry_signature\":\"\",\"link_same_type\":null},\"entry\":{\"content\":\"test entry content\",\"entry_type\":\"testEntryType\"}}]"
+        chain.replay(|p| seen.push(p.entry().entry_type()));
+        assert_eq!(vec![test_type_a(), test_type_b(), test_type_a()], seen);
+    }
+
+    #[test]
+    #[cfg(feature = "no_panic")]
+    /// test that a table error ends iteration and is kept as last_error()
+    fn iter_no_panic() {
+        use hash_table::file::tests::test_table;
+        use std::{fs, path::MAIN_SEPARATOR};
+
+        let (table, dir) = test_table();
+        let mut chain = Chain::new(HashTableActor::new_ref(table));
+
+        let p1 = chain
+            .push_entry(&test_entry_a())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let p2 = chain
+            .push_entry(&test_entry_b())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        // corrupt the stored first pair so that looking it up fails
+        fs::write(
+            format!(
+                "{}{}pairs{}{}.json",
+                dir.path().to_str().unwrap(),
+                MAIN_SEPARATOR,
+                MAIN_SEPARATOR,
+                p1.key(),
+            ),
+            "not json",
+        ).expect("test should be able to write to its temp dir");
+
+        let mut iter = chain.iter();
+        assert_eq!(None, iter.last_error());
+        assert_eq!(Some(p2.clone()), iter.next());
+        assert_eq!(None, iter.next());
+        assert!(iter.last_error().is_some());
+
+        // the header walk never reads the corrupted pair
+        let mut headers = chain.headers();
+        assert_eq!(Some(p2.header().clone()), headers.next());
+        assert_eq!(Some(p1.header().clone()), headers.next());
+        assert_eq!(None, headers.next());
+        assert_eq!(None, headers.last_error());
+
+        // corrupt the stored first header too so that looking it up fails
+        fs::write(
+            format!(
+                "{}{}headers{}{}.json",
+                dir.path().to_str().unwrap(),
+                MAIN_SEPARATOR,
+                MAIN_SEPARATOR,
+                p1.key(),
+            ),
+            "not json",
+        ).expect("test should be able to write to its temp dir");
+
+        let mut headers = chain.headers();
+        assert_eq!(None, headers.last_error());
+        assert_eq!(Some(p2.header().clone()), headers.next());
+        assert_eq!(None, headers.next());
+        assert!(headers.last_error().is_some());
+    }
+
+    #[test]
+    /// test chain.entry_count_by_type()
+    fn entry_count_by_type() {
+        let mut chain = test_chain();
+        assert!(chain.entry_count_by_type().is_empty());
+
+        for e in vec![test_entry_a(), test_entry_b(), test_entry_a()] {
+            chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+
+        let mut expected = HashMap::new();
+        expected.insert(test_type_a(), 2);
+        expected.insert(test_type_b(), 1);
+        assert_eq!(expected, chain.entry_count_by_type());
+    }
+
+    #[test]
+    /// test that chain.entry_count_by_type() reads headers but no entries
+    fn entry_count_by_type_headers_only() {
+        let (chain, table) = test_counting_chain(10);
+
+        assert_eq!(Some(&10), chain.entry_count_by_type().get(&test_type_a()));
+        assert_eq!(0, table.lookups.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    /// test IntoIterator implementation
+    fn into_iter() {
+        let mut chain = test_chain();
+
+        let e1 = test_entry_a();
+        let e2 = test_entry_b();
+        let e3 = test_entry_a();
+
+        let p1 = chain
+            .push_entry(&e1)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let p2 = chain
+            .push_entry(&e2)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let p3 = chain
+            .push_entry(&e3)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        // into_iter() returns clones of pairs
+        assert_eq!(vec![p3, p2, p1], chain.into_iter().collect::<Vec<Pair>>());
+    }
+
+    #[test]
+    /// test chain.common_ancestor()
+    fn common_ancestor() {
+        let mut chain1 = test_chain();
+        let mut chain2 = test_chain();
+
+        assert_eq!(None, chain1.common_ancestor(&chain2));
+
+        let entry_a = test_entry_a();
+        let entry_b = test_entry_b();
+
+        chain1
+            .push_entry(&entry_a)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let pair_b = chain1
+            .push_entry(&entry_b)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        chain2
+            .push_entry(&entry_a)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        chain2
+            .push_entry(&entry_b)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        // identical chains share their top pair
+        assert_eq!(Some(pair_b.clone()), chain1.common_ancestor(&chain2));
+
+        // diverge after the shared prefix
+        chain1
+            .push_entry(&test_entry_unique())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        chain2
+            .push_entry(&test_entry_unique())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        assert_eq!(Some(pair_b.clone()), chain1.common_ancestor(&chain2));
+        assert_eq!(Some(pair_b), chain2.common_ancestor(&chain1));
+
+        // fully disjoint chains share nothing
+        let mut chain3 = test_chain();
+        chain3
+            .push_entry(&test_entry_unique())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(None, chain1.common_ancestor(&chain3));
+    }
+
+    #[test]
+    /// test chain.export_bundle() and Chain::import_bundle()
+    fn bundle_round_trip() {
+        let mut chain = test_chain();
+
+        let empty = chain
+            .export_bundle()
+            .expect("exporting an empty chain shouldn't fail");
+        assert_eq!(None, empty.top);
+        assert!(empty.entries.is_empty());
+
+        for e in vec![test_entry_a(), test_entry_b(), test_entry_a()] {
+            chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+
+        let bundle = chain
+            .export_bundle()
+            .expect("exporting a valid chain shouldn't fail");
+        assert_eq!(chain.top_pair().map(|p| p.key()), bundle.top);
+        assert_eq!(6, bundle.entries.len());
+
+        let imported = Chain::import_bundle(test_table_actor(), &bundle)
+            .expect("importing an exported bundle shouldn't fail");
+        assert_eq!(chain, imported);
+        assert_eq!(
+            chain.iter().collect::<Vec<Pair>>(),
+            imported.iter().collect::<Vec<Pair>>()
+        );
+        assert_eq!(chain.genesis_hash(), imported.genesis_hash());
+
+        // Pairs already in the table are not put again
+        let reimported = Chain::import_bundle(chain.table(), &bundle)
+            .expect("importing a bundle into a table holding its pairs shouldn't fail");
+        assert_eq!(chain, reimported);
+
+        // a bundle missing the top header can't be imported
+        let mut broken = bundle.clone();
+        broken.entries.remove(0);
+        assert!(Chain::import_bundle(test_table_actor(), &broken).is_err());
+    }
+
+    #[test]
+    /// test importing a bundle serialized as JSON
+    fn bundle_json_round_trip() {
+        use std::error::Error;
+
+        let mut chain = test_chain();
+        for e in vec![test_entry_a(), test_entry_b()] {
+            chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+
+        let bundle = chain
+            .export_bundle()
+            .expect("exporting a valid chain shouldn't fail");
+        let json = serde_json::to_string(&bundle).expect("a bundle should serialize");
+        let imported = Chain::import_bundle_json(test_table_actor(), &json)
+            .expect("importing an exported bundle shouldn't fail");
+        assert_eq!(chain, imported);
+        assert_eq!(
+            chain.iter().collect::<Vec<Pair>>(),
+            imported.iter().collect::<Vec<Pair>>()
+        );
+
+        let err = Chain::import_bundle_json(test_table_actor(), "{\"top\":")
+            .expect_err("malformed JSON shouldn't import");
+        assert_eq!("SerializationError", err.variant_name());
+        assert!(err
+            .description()
+            .starts_with("while parsing chain bundle: "));
+    }
+
+    #[test]
+    /// test chain.clone_into_table()
+    fn clone_into_table() {
+        let mut chain = test_chain();
+
+        for e in vec![test_entry_a(), test_entry_b(), test_entry_a()] {
+            chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+
+        let new_table = test_table_actor();
+        let mut cloned = chain
+            .clone_into_table(new_table.clone())
+            .expect("cloning a valid chain into an empty table shouldn't fail");
+
+        assert_eq!(chain, cloned);
+        assert_eq!(
+            chain.iter().collect::<Vec<Pair>>(),
+            cloned.iter().collect::<Vec<Pair>>()
+        );
+
+        // the pairs now live in the new table
+        let top = cloned
+            .top_pair()
+            .expect("cloned chain should have a top pair");
+        assert_eq!(
+            Some(top.clone()),
+            new_table
+                .pair(&top.key())
+                .expect("getting a pair from a table shouldn't fail")
+        );
+
+        // the clone uses a different table so pushing to it leaves the original untouched
+        let pair = cloned
+            .push_entry(&test_entry_unique())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(
+            None,
+            chain
+                .table()
+                .pair(&pair.key())
+                .expect("getting a pair from a table shouldn't fail")
+        );
+        assert_eq!(Some(top), chain.top_pair());
+    }
+
+    #[test]
+    /// test chain.find_entry_by_content()
+    fn find_entry_by_content() {
+        let mut chain = test_chain();
+
+        let e1 = Entry::new(&test_type_a(), "alice");
+        let e2 = Entry::new(&test_type_a(), "bob");
+        for e in vec![&e1, &e2] {
+            chain
+                .push_entry(e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+
+        assert_eq!(
+            Some(e1),
+            chain.find_entry_by_content(&test_type_a(), "alice")
+        );
+        assert_eq!(Some(e2), chain.find_entry_by_content(&test_type_a(), "bob"));
+        assert_eq!(None, chain.find_entry_by_content(&test_type_b(), "bob"));
+        assert_eq!(None, chain.find_entry_by_content(&test_type_a(), "carol"));
+    }
+
+    #[test]
+    /// test chain.truncate_to_length()
+    fn truncate_to_length() {
+        let mut chain = test_chain();
+
+        let mut pairs = Vec::new();
+        for _ in 0..5 {
+            pairs.push(
+                chain
+                    .push_entry(&test_entry_unique())
+                    .expect("pushing a valid entry to an exlusively owned chain shouldn't fail"),
+            );
+        }
+
+        let truncated = chain
+            .truncate_to_length(2)
+            .expect("truncating a valid chain shouldn't fail");
+        assert_eq!(
+            vec![pairs[1].clone(), pairs[0].clone()],
+            truncated.iter().collect::<Vec<Pair>>()
+        );
+        assert_eq!(chain.genesis_hash(), truncated.genesis_hash());
+        // the truncated chain has its own top pair
+        assert_eq!(Some(pairs[4].clone()), chain.top_pair());
+        // the truncated pairs are still in the table
+        assert_eq!(Ok(Some(pairs[4].clone())), truncated.pair(&pairs[4].key()));
+
+        assert_eq!(
+            chain,
+            chain
+                .truncate_to_length(5)
+                .expect("truncating a valid chain shouldn't fail")
+        );
+        assert_eq!(
+            None,
+            chain
+                .truncate_to_length(0)
+                .expect("truncating a valid chain shouldn't fail")
+                .top_pair()
+        );
+    }
+
+    #[test]
+    /// test chain.rewind()
+    fn rewind() {
+        let mut chain = test_chain();
+        let mut pairs = Vec::new();
+        for _ in 0..4 {
+            pairs.push(
+                chain
+                    .push_entry(&test_entry_unique())
+                    .expect("pushing a valid entry to an exlusively owned chain shouldn't fail"),
+            );
+        }
+
+        let rewound = chain.rewind(2).expect("rewinding a chain shouldn't fail");
+        assert_eq!(Some(pairs[1].clone()), rewound.top_pair());
+        assert_eq!(2, rewound.iter().count());
+        assert_eq!(chain.genesis_hash(), rewound.genesis_hash());
+        // the original chain is untouched
+        assert_eq!(Some(pairs[3].clone()), chain.top_pair());
+
+        assert_eq!(chain, chain.rewind(0).unwrap());
+        assert_eq!(None, chain.rewind(4).unwrap().top_pair());
+        assert_eq!(None, chain.rewind(10).unwrap().top_pair());
+        assert_eq!(None, chain.rewind(10).unwrap().genesis_hash());
+    }
+
+    #[test]
+    /// test chain.checkpoint() and chain.restore()
+    fn checkpoint_restore() {
+        let mut chain = test_chain();
+        let empty = chain.checkpoint();
+        for _ in 0..2 {
+            chain
+                .push_entry(&test_entry_unique())
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+        let checkpoint = chain.checkpoint();
+        assert_eq!(chain.top_pair(), checkpoint.top_pair());
+
+        let mut rolled_back = Vec::new();
+        for _ in 0..2 {
+            rolled_back.push(
+                chain
+                    .push_entry(&test_entry_unique())
+                    .expect("pushing a valid entry to an exlusively owned chain shouldn't fail"),
+            );
+        }
+
+        let genesis_hash = chain.genesis_hash();
+        let commits = chain.subscribe_commits();
+        assert_eq!(Ok(()), chain.restore(&checkpoint));
+        assert_eq!(checkpoint.top_pair(), chain.top_pair());
+        assert_eq!(genesis_hash, chain.genesis_hash());
+        // restoring commits nothing
+        assert!(commits.try_recv().is_err());
+        assert_eq!(2, chain.iter().count());
+        // rolled back pairs stay in the table
+        for p in rolled_back {
+            assert_eq!(Some(p.clone()), chain.table().pair(&p.key()).unwrap());
+        }
+
+        assert_eq!(Ok(()), chain.restore(&empty));
+        assert_eq!(None, chain.top_pair());
+        assert_eq!(None, chain.genesis_hash());
+        assert_eq!(
+            Err(HolochainError::new(&format!(
+                "checkpoint {} is not in the chain",
+                checkpoint.top_pair().unwrap().key()
+            ))),
+            chain.restore(&checkpoint)
+        );
+    }
+
+    #[test]
+    /// test chain.merkle_root()
+    fn merkle_root() {
+        let mut chain1 = test_chain();
+        let mut chain2 = test_chain();
+        assert_eq!(chain1.merkle_root(), chain2.merkle_root());
+
+        for chain in vec![&mut chain1, &mut chain2] {
+            chain
+                .push_entry(&test_entry_a())
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+        assert_eq!(chain1.merkle_root(), chain2.merkle_root());
+
+        let root = chain1.merkle_root();
+        chain1
+            .push_entry(&test_entry_b())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_ne!(root, chain1.merkle_root());
+        assert_ne!(chain1.merkle_root(), chain2.merkle_root());
+    }
+
+    #[test]
+    /// test chain.entries_since()
+    fn entries_since() {
+        let mut chain = test_chain();
+
+        let mut pairs = Vec::new();
+        for _ in 0..4 {
+            pairs.push(
+                chain
+                    .push_entry(&test_entry_unique())
+                    .expect("pushing a valid entry to an exlusively owned chain shouldn't fail"),
+            );
+        }
+
+        assert_eq!(
+            Ok(vec![pairs[2].clone(), pairs[3].clone()]),
+            chain.entries_since(&pairs[1].key())
+        );
+        assert_eq!(Ok(vec![]), chain.entries_since(&pairs[3].key()));
+        assert!(chain.entries_since(&test_pair_unique().key()).is_err());
+    }
+
+    #[test]
+    /// test chain.ancestors_of()
+    fn ancestors_of() {
+        let mut chain = test_chain();
+
+        let mut pairs = Vec::new();
+        for _ in 0..4 {
+            pairs.push(
+                chain
+                    .push_entry(&test_entry_unique())
+                    .expect("pushing a valid entry to an exlusively owned chain shouldn't fail"),
+            );
+        }
+
+        assert_eq!(
+            Ok(vec![pairs[2].clone(), pairs[1].clone()]),
+            chain.ancestors_of(&pairs[3].key(), 2)
+        );
+        assert_eq!(
+            Ok(vec![pairs[0].clone()]),
+            chain.ancestors_of(&pairs[1].key(), 2)
+        );
+        assert_eq!(Ok(vec![]), chain.ancestors_of(&pairs[0].key(), 2));
+        assert!(chain.ancestors_of(&test_pair_unique().key(), 2).is_err());
+    }
+
+    #[test]
+    /// test chain.link_targets()
+    fn link_targets() {
+        let mut chain = test_chain();
+
+        let mut pairs = Vec::new();
+        for e in vec![
+            test_entry_a(),
+            test_entry_b(),
+            test_entry_b(),
+            test_entry_a(),
+        ] {
+            pairs.push(
+                chain
+                    .push_entry(&e)
+                    .expect("pushing a valid entry to an exlusively owned chain shouldn't fail"),
+            );
+        }
+
+        assert_eq!(Ok((None, None)), chain.link_targets(&pairs[0].key()));
+        assert_eq!(
+            Ok((Some(pairs[0].clone()), None)),
+            chain.link_targets(&pairs[1].key())
+        );
+        assert_eq!(
+            Ok((Some(pairs[1].clone()), Some(pairs[1].clone()))),
+            chain.link_targets(&pairs[2].key())
+        );
+        assert_eq!(
+            Ok((Some(pairs[2].clone()), Some(pairs[0].clone()))),
+            chain.link_targets(&pairs[3].key())
+        );
+        assert!(chain.link_targets(&test_pair_unique().key()).is_err());
+    }
+
+    #[test]
+    /// test chain.iter_with_depth()
+    fn iter_with_depth() {
+        let mut chain = test_chain();
+        assert_eq!(0, chain.iter_with_depth().count());
+
+        let mut pairs = Vec::new();
+        for e in vec![test_entry_a(), test_entry_b(), test_entry_unique()] {
+            pairs.push(
+                chain
+                    .push_entry(&e)
+                    .expect("pushing a valid entry to an exlusively owned chain shouldn't fail"),
+            );
+        }
+        pairs.reverse();
+
+        assert_eq!(
+            vec![
+                (0, pairs[0].clone()),
+                (1, pairs[1].clone()),
+                (2, pairs[2].clone())
+            ],
+            chain.iter_with_depth().collect::<Vec<(usize, Pair)>>()
+        );
+    }
+
+    #[test]
+    /// test chain.entry_addresses()
+    fn entry_addresses() {
+        let mut chain = test_chain();
+        assert!(chain.entry_addresses().is_empty());
+
+        for e in vec![test_entry_a(), test_entry_b(), test_entry_a()] {
+            chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+
+        assert_eq!(
+            vec![test_entry_a().hash(), test_entry_b().hash()],
+            chain.entry_addresses()
+        );
+    }
+
+    #[test]
+    /// test chain.entry_handles()
+    fn entry_handles() {
+        let mut chain = test_chain();
+        assert!(chain.entry_handles().is_empty());
+
+        let entries = vec![test_entry_a(), test_entry_b(), test_entry_unique()];
+        for e in entries.iter() {
+            chain
+                .push_entry(e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+
+        let handles = chain.entry_handles();
+        assert_eq!(
+            entries
+                .iter()
+                .rev()
+                .map(|e| (e.hash(), e.entry_type()))
+                .collect::<Vec<(String, String)>>(),
+            handles
+                .iter()
+                .map(|h| (h.entry_address().to_string(), h.entry_type().to_string()))
+                .collect::<Vec<(String, String)>>(),
+        );
+
+        assert_eq!(Ok(test_entry_b()), handles[1].load());
+    }
+
+    #[test]
+    /// test that chain.entry_handles() reads no entries until a handle is loaded
+    fn entry_handles_lazy() {
+        let (chain, table) = test_counting_chain(10);
+
+        let handles = chain.entry_handles();
+        assert_eq!(10, handles.len());
+        assert_eq!(0, table.lookups.load(Ordering::SeqCst));
+
+        handles[5]
+            .load()
+            .expect("loading the entry of a pair in the table shouldn't fail");
+        assert_eq!(1, table.lookups.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    /// test chain.get_entries()
+    fn get_entries() {
+        let mut chain = test_chain();
+        assert!(chain.get_entries(&[test_entry_a().hash()]).is_empty());
+
+        for e in vec![test_entry_a(), test_entry_b(), test_entry_a()] {
+            chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+
+        let entries = chain.get_entries(&[
+            test_entry_a().hash(),
+            test_entry_unique().hash(),
+            test_entry_b().hash(),
+        ]);
+        let mut expected = HashMap::new();
+        expected.insert(test_entry_a().hash(), test_entry_a());
+        expected.insert(test_entry_b().hash(), test_entry_b());
+        assert_eq!(expected, entries);
+    }
+
+    #[test]
+    /// test that chain.get_entries() fetches the entries found as one batch
+    fn get_entries_batch() {
+        let (chain, table) = test_counting_chain(10);
+        let addresses = chain.entry_addresses();
+        table.reset();
+
+        assert_eq!(10, chain.get_entries(&addresses).len());
+        assert_eq!(0, table.lookups.load(Ordering::SeqCst));
+        assert_eq!(1, table.batch_lookups.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    /// test chain.entries_between()
+    fn entries_between() {
+        let mut chain = test_chain();
+        let mut pairs = Vec::new();
+        for _ in 0..4 {
+            pairs.push(
+                chain
+                    .push_entry(&test_entry_unique())
+                    .expect("pushing a valid entry to an exlusively owned chain shouldn't fail"),
+            );
+        }
+        let (a, b, c, d) = (&pairs[0], &pairs[1], &pairs[2], &pairs[3]);
+
+        assert_eq!(
+            Ok(vec![d.clone(), c.clone(), b.clone()]),
+            chain.entries_between(&b.key(), &d.key())
+        );
+        assert_eq!(
+            Ok(vec![a.clone()]),
+            chain.entries_between(&a.key(), &a.key())
+        );
+
+        assert_eq!(
+            Err(HolochainError::new(&format!(
+                "pair {} is newer than pair {}",
+                d.key(),
+                b.key()
+            ))),
+            chain.entries_between(&d.key(), &b.key())
+        );
+        let missing = test_pair_unique();
+        assert_eq!(
+            Err(HolochainError::new(&format!(
+                "pair {} is not in the chain",
+                missing.key()
+            ))),
+            chain.entries_between(&a.key(), &missing.key())
+        );
+        assert_eq!(
+            Err(HolochainError::new(&format!(
+                "pair {} is not in the chain",
+                missing.key()
+            ))),
+            chain.entries_between(&missing.key(), &d.key())
+        );
+    }
+
+    #[test]
+    /// test chain.count_matching()
+    fn count_matching() {
+        let mut chain = test_chain();
+        assert_eq!(0, chain.count_matching(|_| true));
+
+        for e in vec![test_entry_a(), test_entry_b(), test_entry_unique()] {
+            chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+
+        let is_type_a = |h: &Header| h.entry_type() == test_type_a();
+        assert_eq!(
+            chain.iter().filter(|p| is_type_a(p.header())).count(),
+            chain.count_matching(is_type_a)
+        );
+        assert_eq!(2, chain.count_matching(is_type_a));
+        assert_eq!(1, chain.count_matching(|h| h.entry_type() == test_type_b()));
+        assert_eq!(3, chain.count_matching(|_| true));
+    }
+
+    #[test]
+    /// test that chain.count_matching() reads headers but no entries
+    fn count_matching_headers_only() {
+        let (chain, table) = test_counting_chain(10);
+
+        assert_eq!(10, chain.count_matching(|_| true));
+        assert_eq!(0, table.lookups.load(Ordering::SeqCst));
+        // the top header is held by the chain, every older one is looked up once
+        assert_eq!(9, table.header_lookups.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    /// test chain.take_while_pairs()
+    fn take_while_pairs() {
+        let mut chain = test_chain();
+        let is_a = |p: &Pair| p.entry().entry_type() == test_type_a();
+
+        assert_eq!(Vec::<Pair>::new(), chain.take_while_pairs(is_a));
+
+        let mut pairs = Vec::new();
+        for e in vec![
+            test_entry_a(),
+            test_entry_a(),
+            test_entry_b(),
+            test_entry_a(),
+        ] {
+            pairs.push(
+                chain
+                    .push_entry(&e)
+                    .expect("pushing a valid entry to an exlusively owned chain shouldn't fail"),
+            );
+        }
+
+        assert_eq!(vec![pairs[3].clone()], chain.take_while_pairs(is_a));
+
+        chain
+            .push_entry(&test_entry_a())
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        assert_eq!(2, chain.take_while_pairs(is_a).len());
+        assert_eq!(5, chain.take_while_pairs(|_| true).len());
+    }
+
+    #[test]
+    /// test chain.head_at_time()
+    fn head_at_time() {
+        let mut chain = test_chain();
+        let at = |s: &str| s.parse::<DateTime<Utc>>().expect("test time should parse");
+
+        assert_eq!(None, chain.head_at_time(&at("2018-02-15T00:00:00Z")));
+
+        let mut pairs = Vec::new();
+        for t in vec![
+            "2018-01-01T00:00:00Z",
+            "2018-02-01T00:00:00Z",
+            "2018-03-01T00:00:00Z",
+        ] {
+            let pair = test_pair_with_timestamp(&chain, &test_entry_unique(), t);
+            pairs.push(
+                chain
+                    .push_pair(&pair)
+                    .expect("pushing a valid pair to an exlusively owned chain shouldn't fail"),
+            );
+        }
+
+        assert_eq!(
+            Some(&pairs[1]),
+            chain.head_at_time(&at("2018-02-15T00:00:00Z")).as_ref()
+        );
+        assert_eq!(
+            Some(&pairs[2]),
+            chain.head_at_time(&at("2018-03-01T00:00:00Z")).as_ref()
+        );
+        assert_eq!(None, chain.head_at_time(&at("2017-12-31T00:00:00Z")));
+    }
+
+    #[test]
+    /// test chain.validate_monotonic_timestamps()
+    fn validate_monotonic_timestamps() {
+        let push = |chain: &mut Chain, timestamp: &str| {
+            let pair = test_pair_with_timestamp(chain, &test_entry_unique(), timestamp);
+            chain
+                .push_pair(&pair)
+                .expect("pushing a valid pair to an exlusively owned chain shouldn't fail")
+        };
+
+        let mut chain = test_chain();
+        for t in vec!["2018-01-01T00:00:00Z", "", "2018-02-01T00:00:00Z"] {
+            push(&mut chain, t);
+        }
+        assert_eq!(Ok(()), chain.validate_monotonic_timestamps());
+
+        let p = push(&mut chain, "2018-01-15T00:00:00Z");
+        assert_eq!(
+            Err(HolochainError::new(&format!(
+                "timestamp 2018-01-15T00:00:00Z for pair {} is earlier than the previous pair",
+                p.key(),
+            ))),
+            chain.validate_monotonic_timestamps()
+        );
+    }
+
+    #[test]
+    /// test to_json() and from_json() implementation
+    fn json_round_trip() {
+        let mut chain = test_chain();
+
+        let e1 = test_entry_a();
+        let e2 = test_entry_b();
+        let e3 = test_entry_a();
+
+        chain
+            .push_entry(&e1)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        chain
+            .push_entry(&e2)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        chain
+            .push_entry(&e3)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        let expected_json = "[{\"header\":{\"entry_type\":\"testEntryType\",\"timestamp\":\"\",\"link\":\"QmPT5HXvyv54Dg36YSK1A2rYvoPCNWoqpLzzZnHnQBcU6x\",\"entry_hash\":\"QmbXSE38SN3SuJDmHKSSw5qWWegvU7oTxrLDRavWjyxMrT\",\"entry_signature\":\"\",\"link_same_type\":\"QmawqBCVVap9KdaakqEHF4JzUjjLhmR7DpM5jgJko8j1rA\"},\"entry\":{\"content\":\"test entry content\",\"entry_type\":\"testEntryType\"}},{\"header\":{\"entry_type\":\"testEntryTypeB\",\"timestamp\":\"\",\"link\":\"QmawqBCVVap9KdaakqEHF4JzUjjLhmR7DpM5jgJko8j1rA\",\"entry_hash\":\"QmPz5jKXsxq7gPVAbPwx5gD2TqHfqB8n25feX5YH18JXrT\",\"entry_signature\":\"\",\"link_same_type\":null},\"entry\":{\"content\":\"other test entry content\",\"entry_type\":\"testEntryTypeB\"}},{\"header\":{\"entry_type\":\"testEntryType\",\"timestamp\":\"\",\"link\":null,\"entry_hash\":\"QmbXSE38SN3SuJDmHKSSw5qWWegvU7oTxrLDRavWjyxMrT\",\"entry_signature\":\"\",\"link_same_type\":null},\"entry\":{\"content\":\"test entry content\",\"entry_type\":\"testEntryType\"}}]"
         ;
         assert_eq!(
             expected_json,
@@ -666,4 +3497,60 @@ pub mod tests {
         assert_eq!(chain, Chain::from_json(table_actor, expected_json));
     }
 
+    #[test]
+    /// test that chain.from_json_streaming() rebuilds a chain from its JSON
+    fn from_json_streaming() {
+        use std::error::Error;
+
+        let chain = test_deterministic_chain(&[test_entry_a(), test_entry_b(), test_entry_a()]);
+        let json = chain.to_json().expect("chain shouldn't fail to serialize");
+
+        let streamed = Chain::from_json_streaming(test_table_actor(), Cursor::new(json))
+            .expect("streaming valid chain json shouldn't fail");
+        assert_eq!(chain, streamed);
+        assert_eq!(
+            chain.iter().collect::<Vec<Pair>>(),
+            streamed.iter().collect::<Vec<Pair>>()
+        );
+
+        // JSON that isn't an array of pairs is a serialization error
+        let err = Chain::from_json_streaming(test_table_actor(), Cursor::new("{}"))
+            .expect_err("JSON that isn't an array shouldn't stream");
+        assert_eq!("SerializationError", err.variant_name());
+        assert!(err.description().starts_with("while parsing chain json: "));
+    }
+
+    #[test]
+    /// test that chain.from_json_streaming() reads the Pairs back out of the table in batches
+    fn from_json_streaming_batches() {
+        let (chain, _) = test_counting_chain(STREAMING_BATCH_SIZE * 2 + 1);
+        let json = chain.to_json().expect("chain shouldn't fail to serialize");
+
+        let table = CountingTable::new();
+        let streamed =
+            Chain::from_json_streaming(HashTableActor::new_ref(table.clone()), Cursor::new(json))
+                .expect("streaming valid chain json shouldn't fail");
+        assert_eq!(chain, streamed);
+        assert_eq!(3, table.batch_lookups.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    /// test that chain.to_json_pretty() is the same JSON as chain.to_json()
+    fn to_json_pretty() {
+        let mut chain = test_chain();
+        for e in vec![test_entry_a(), test_entry_b()] {
+            chain
+                .push_entry(&e)
+                .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        }
+
+        let pretty = chain
+            .to_json_pretty()
+            .expect("chain shouldn't fail to serialize");
+        assert!(pretty.contains('\n'));
+
+        let parse = |s: &str| serde_json::from_str::<serde_json::Value>(s).unwrap();
+        assert_eq!(parse(&chain.to_json().unwrap()), parse(&pretty));
+    }
+
 }
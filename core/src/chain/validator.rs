@@ -0,0 +1,29 @@
+use chain::Chain;
+use error::HolochainError;
+use hash_table::entry::Entry;
+use std::fmt;
+
+/// validation rules for entries of one entry type
+/// registered on a Chain with Chain::with_entry_validator() and run before every push of an
+/// entry of that type
+pub trait EntryValidator: Send + Sync {
+    /// returns an error if the entry may not be pushed to the chain
+    fn validate(&self, entry: &Entry, chain: &Chain) -> Result<(), HolochainError>;
+}
+
+/// any closure with the same signature as validate() is a validator
+impl<F> EntryValidator for F
+where
+    F: Fn(&Entry, &Chain) -> Result<(), HolochainError> + Send + Sync,
+{
+    fn validate(&self, entry: &Entry, chain: &Chain) -> Result<(), HolochainError> {
+        self(entry, chain)
+    }
+}
+
+/// validators are opaque, this lets Chain keep deriving Debug
+impl fmt::Debug for EntryValidator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EntryValidator")
+    }
+}
@@ -1,6 +1,8 @@
 use error::HolochainError;
 use holochain_agent::Agent;
+use instance::ObserverLimit;
 use logger::Logger;
+use network::Network;
 use persister::Persister;
 use std::sync::{Arc, Mutex};
 
@@ -10,6 +12,16 @@ pub struct Context {
     pub agent: Agent,
     pub logger: Arc<Mutex<Logger>>,
     pub persister: Arc<Mutex<Persister>>,
+    /// when true, reducers log every action they handle through the logger
+    pub log_actions: bool,
+    /// where gets go when an entry is not in the local chain, None for a local only instance
+    pub network: Option<Arc<Network>>,
+    /// largest result in bytes that a zome API function may write back to wasm memory
+    /// None for no limit other than the free memory
+    pub max_result_size: Option<usize>,
+    /// cap on the observers zome API functions register while waiting on their actions
+    /// replaced by the limit of the instance the context is started with
+    pub observer_limit: ObserverLimit,
 }
 
 impl Context {
@@ -19,4 +31,20 @@ impl Context {
         logger.log(msg.to_string());
         Ok(())
     }
+
+    /// returns a copy of this context that uses the given network
+    pub fn with_network(&self, network: Arc<Network>) -> Context {
+        Context {
+            network: Some(network),
+            ..self.clone()
+        }
+    }
+
+    /// returns a copy of this context that limits zome API function results to the given size
+    pub fn with_max_result_size(&self, max_result_size: usize) -> Context {
+        Context {
+            max_result_size: Some(max_result_size),
+            ..self.clone()
+        }
+    }
 }
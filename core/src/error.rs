@@ -1,11 +1,13 @@
 use self::HolochainError::*;
 use json::ToJson;
+use serde_json;
 use serde_json::Error as SerdeError;
 use std::{
     error::Error,
     fmt,
     io::{self, Error as IoError},
     path::Path,
+    sync::mpsc::RecvTimeoutError,
 };
 use walkdir::Error as WalkdirError;
 
@@ -25,17 +27,69 @@ pub enum HolochainError {
     IoError(String),
     SerializationError(String),
     InvalidOperationOnSysEntry,
+    Timeout,
+    Busy,
+    /// content rejected by validation, field names the offending part of it, if any
+    ValidationError {
+        field: Option<String>,
+        message: String,
+    },
+    /// the message of an error prefixed with what was being done when it happened
+    /// built with with_context(), the second field is the original error
+    Context(String, Box<HolochainError>),
 }
 
 impl HolochainError {
     pub fn new(msg: &str) -> HolochainError {
         HolochainError::ErrorGeneric(msg.to_string())
     }
+
+    /// wraps the error with a description of what was being done when it happened
+    /// the context is prepended to the message, e.g. "while setting top pair: <message>", so
+    /// nested contexts read outermost first
+    pub fn with_context(self, msg: &str) -> HolochainError {
+        Context(format!("{}: {}", msg, self.description()), Box::new(self))
+    }
+
+    /// stable identifier for the kind of error, suitable for grouping in metrics
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            ErrorGeneric(_) => "ErrorGeneric",
+            InstanceNotActive => "InstanceNotActive",
+            InstanceActive => "InstanceActive",
+            NotImplemented => "NotImplemented",
+            LoggingError => "LoggingError",
+            DnaMissing => "DnaMissing",
+            ZomeNotFound(_) => "ZomeNotFound",
+            CapabilityNotFound(_) => "CapabilityNotFound",
+            ZomeFunctionNotFound(_) => "ZomeFunctionNotFound",
+            IoError(_) => "IoError",
+            SerializationError(_) => "SerializationError",
+            InvalidOperationOnSysEntry => "InvalidOperationOnSysEntry",
+            Timeout => "Timeout",
+            Busy => "Busy",
+            ValidationError { .. } => "ValidationError",
+            // group by the original error, not by where it was caught
+            Context(_, err) => err.variant_name(),
+        }
+    }
 }
 
 impl ToJson for HolochainError {
     fn to_json(&self) -> Result<String, HolochainError> {
-        Ok(format!("{{\"error\":\"{}\"}}", self.description()))
+        // validation errors keep the field separate so guests can show it next to the input
+        if let ValidationError { field, message } = self {
+            return Ok(format!(
+                "{{\"error\":\"validation\",\"field\":{},\"message\":{}}}",
+                serde_json::to_string(field)?,
+                serde_json::to_string(message)?
+            ));
+        }
+        Ok(format!(
+            "{{\"error\":\"{}\",\"error_kind\":\"{}\"}}",
+            self.description(),
+            self.variant_name()
+        ))
     }
 }
 
@@ -64,6 +118,10 @@ impl Error for HolochainError {
             IoError(err_msg) => &err_msg,
             SerializationError(err_msg) => &err_msg,
             InvalidOperationOnSysEntry => "operation cannot be done on a system entry type",
+            Timeout => "timed out waiting for a response",
+            Busy => "too many observers are registered, try again later",
+            ValidationError { message, .. } => &message,
+            Context(err_msg, _) => &err_msg,
         }
     }
 }
@@ -101,9 +159,20 @@ impl From<SerdeError> for HolochainError {
     }
 }
 
+impl From<RecvTimeoutError> for HolochainError {
+    fn from(error: RecvTimeoutError) -> Self {
+        match error {
+            RecvTimeoutError::Timeout => HolochainError::Timeout,
+            RecvTimeoutError::Disconnected => HolochainError::ErrorGeneric(error.to_string()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json;
+    use std::{sync::mpsc::channel, time::Duration};
     // a test function that returns our error result
     fn raises_holochain_error(yes: bool) -> Result<(), HolochainError> {
         if yes {
@@ -124,7 +193,83 @@ mod tests {
     /// test that we can convert an error to valid JSON
     fn test_to_json() {
         let err = HolochainError::new("foo");
-        assert_eq!(r#"{"error":"foo"}"#, err.to_json().unwrap());
+        assert_eq!(
+            r#"{"error":"foo","error_kind":"ErrorGeneric"}"#,
+            err.to_json().unwrap()
+        );
+    }
+
+    #[test]
+    /// test that validation errors keep their field in the JSON
+    fn validation_error_to_json() {
+        let err = HolochainError::ValidationError {
+            field: Some("content".to_string()),
+            message: "content is too \"long\"".to_string(),
+        };
+        assert_eq!("ValidationError", err.variant_name());
+        assert_eq!("content is too \"long\"", err.description());
+        assert_eq!(
+            r#"{"error":"validation","field":"content","message":"content is too \"long\""}"#,
+            err.to_json().unwrap()
+        );
+
+        let err = HolochainError::ValidationError {
+            field: None,
+            message: "rejected".to_string(),
+        };
+        assert_eq!(
+            r#"{"error":"validation","field":null,"message":"rejected"}"#,
+            err.to_json().unwrap()
+        );
+    }
+
+    #[test]
+    /// test that context is prepended to the message and included in the JSON
+    fn with_context() {
+        let err = HolochainError::new("borked")
+            .with_context("while putting pair")
+            .with_context("while committing");
+
+        assert_eq!(
+            "while committing: while putting pair: borked",
+            err.description()
+        );
+        assert_eq!("ErrorGeneric", err.variant_name());
+        assert_eq!(
+            r#"{"error":"while committing: while putting pair: borked","error_kind":"ErrorGeneric"}"#,
+            err.to_json().unwrap()
+        );
+    }
+
+    #[test]
+    /// test that every error reports a stable variant name
+    fn variant_name() {
+        assert_eq!(
+            "ZomeNotFound",
+            HolochainError::ZomeNotFound("foo".to_string()).variant_name()
+        );
+        assert_eq!("ErrorGeneric", HolochainError::new("foo").variant_name());
+        assert_eq!("DnaMissing", HolochainError::DnaMissing.variant_name());
+    }
+
+    #[test]
+    /// test that bad JSON converts to a serialization error
+    fn from_serde_error() {
+        let result: Result<String, SerdeError> = serde_json::from_str("not json");
+        match HolochainError::from(result.expect_err("bad JSON should not parse")) {
+            HolochainError::SerializationError(_) => {}
+            err => panic!("expected a SerializationError, got {:?}", err),
+        };
+    }
+
+    #[test]
+    /// test that channel timeouts convert to timeout errors
+    fn from_recv_timeout_error() {
+        let (_sender, receiver) = channel::<()>();
+        let err = receiver
+            .recv_timeout(Duration::from_millis(1))
+            .expect_err("nothing was sent so receiving should time out");
+        assert_eq!(HolochainError::Timeout, HolochainError::from(err));
     }
 
     #[test]
@@ -19,6 +19,49 @@ pub fn serializable_to_b58_hash<S: Serialize>(s: S, hash_type: Hash) -> String {
     str_to_b58_hash(&serde_json::to_string(&s).unwrap(), hash_type)
 }
 
+/// convert a string as bytes to the lowercase hex of its plain SHA-256 digest
+pub fn str_to_sha256_hex(s: &str) -> String {
+    // a multihash is the hash code and digest length followed by the digest itself
+    encode(Hash::SHA2256, s.as_bytes()).unwrap()[2..]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// the ways entries and headers can be hashed into keys
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HashAlgorithm {
+    /// b58 SHA-256 multihash, e.g. Qm...
+    Sha256Multihash,
+    /// hex SHA-256 digest without a multihash prefix, for systems expecting plain SHA-256
+    Sha256Hex,
+    /// b58 Blake2b multihash
+    Blake2b,
+}
+
+impl Default for HashAlgorithm {
+    fn default() -> HashAlgorithm {
+        HashAlgorithm::Sha256Multihash
+    }
+}
+
+impl HashAlgorithm {
+    /// hash a string as bytes with this algorithm
+    pub fn hash_str(&self, s: &str) -> String {
+        match self {
+            HashAlgorithm::Sha256Multihash => str_to_b58_hash(s, Hash::SHA2256),
+            HashAlgorithm::Sha256Hex => str_to_sha256_hex(s),
+            HashAlgorithm::Blake2b => str_to_b58_hash(s, Hash::Blake2b),
+        }
+    }
+
+    /// true for the default algorithm
+    /// data hashed with the default algorithm doesn't serialize its algorithm
+    pub fn is_default(&self) -> bool {
+        *self == HashAlgorithm::default()
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -49,6 +92,36 @@ pub mod tests {
         );
     }
 
+    #[test]
+    /// known plain SHA-256 hex digest
+    fn str_to_sha256_hex_known() {
+        assert_eq!(
+            "916f0027a575074ce72a331777c3478d6513f786a591bd892da1a577bf2335f9",
+            str_to_sha256_hex("test data"),
+        );
+    }
+
+    #[test]
+    /// tests for HashAlgorithm::hash_str()
+    fn hash_algorithm_hash_str() {
+        assert_eq!(
+            "QmY8Mzg9F69e5P9AoQPYat655HEhc1TVGs11tmfNSzkqh2",
+            HashAlgorithm::default().hash_str("test data"),
+        );
+        assert_eq!(
+            str_to_sha256_hex("test data"),
+            HashAlgorithm::Sha256Hex.hash_str("test data"),
+        );
+        assert_eq!(
+            str_to_b58_hash("test data", Hash::Blake2b),
+            HashAlgorithm::Blake2b.hash_str("test data"),
+        );
+        assert_ne!(
+            HashAlgorithm::default().hash_str("test data"),
+            HashAlgorithm::Blake2b.hash_str("test data"),
+        );
+    }
+
     #[test]
     /// known hash for a serializable something
     fn can_serialize_to_b58_hash() {
@@ -1,7 +1,9 @@
 use actor::{AskSelf, Protocol, SYS};
 use agent::keys::Keys;
+use chain::header::Header;
 use error::HolochainError;
-use hash_table::{pair::Pair, pair_meta::PairMeta, HashTable};
+use hash_table::{pair::Pair, pair_meta::PairMeta, HashTable, TableStats};
+use key::Key;
 use riker::actors::*;
 use snowflake;
 
@@ -27,11 +29,26 @@ impl HashTable for ActorRef<Protocol> {
         unwrap_to!(response => Protocol::PutPairResult).clone()
     }
 
+    fn put_pairs(&mut self, pairs: &[Pair]) -> Result<(), HolochainError> {
+        let response = self.block_on_ask(Protocol::PutPairBatch(pairs.to_vec()));
+        unwrap_to!(response => Protocol::PutPairBatchResult).clone()
+    }
+
     fn pair(&self, key: &str) -> Result<Option<Pair>, HolochainError> {
         let response = self.block_on_ask(Protocol::GetPair(key.to_string()));
         unwrap_to!(response => Protocol::GetPairResult).clone()
     }
 
+    fn pairs(&self, keys: &[String]) -> Result<Vec<Option<Pair>>, HolochainError> {
+        let response = self.block_on_ask(Protocol::GetPairBatch(keys.to_vec()));
+        unwrap_to!(response => Protocol::GetPairBatchResult).clone()
+    }
+
+    fn header(&self, key: &str) -> Result<Option<Header>, HolochainError> {
+        let response = self.block_on_ask(Protocol::GetHeader(key.to_string()));
+        unwrap_to!(response => Protocol::GetHeaderResult).clone()
+    }
+
     fn modify_pair(
         &mut self,
         keys: &Keys,
@@ -68,6 +85,11 @@ impl HashTable for ActorRef<Protocol> {
         let response = self.block_on_ask(Protocol::GetMetasForPair(pair.clone()));
         unwrap_to!(response => Protocol::GetMetasForPairResult).clone()
     }
+
+    fn stats(&self) -> Result<TableStats, HolochainError> {
+        let response = self.block_on_ask(Protocol::Stats);
+        unwrap_to!(response => Protocol::StatsResult).clone()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -99,6 +121,28 @@ impl<HT: HashTable> HashTableActor<HT> {
             &snowflake::ProcessUniqueId::new().to_string(),
         ).unwrap()
     }
+
+    /// puts the pair in the table unless the table already has it
+    /// pairs are content addressed, so a different pair under the same key is a hash collision
+    /// and returns an error without overwriting anything
+    /// entry metadata is not part of the hash, so a pair that only differs by metadata is the
+    /// same pair and overwrites the stored one to update its metadata
+    fn upsert_pair(&mut self, pair: &Pair) -> Result<(), HolochainError> {
+        match self.table.pair(&pair.key())? {
+            Some(ref existing) if existing == pair => {
+                if existing.entry().metadata() == pair.entry().metadata() {
+                    Ok(())
+                } else {
+                    self.table.put_pair(pair)
+                }
+            }
+            Some(_) => Err(HolochainError::new(&format!(
+                "hash collision: a different pair is already stored under {}",
+                pair.key()
+            ))),
+            None => self.table.put_pair(pair),
+        }
+    }
 }
 
 impl<HT: HashTable> Actor for HashTableActor<HT> {
@@ -118,10 +162,23 @@ impl<HT: HashTable> Actor for HashTableActor<HT> {
 
                     Protocol::Teardown => Protocol::TeardownResult(self.table.teardown()),
 
-                    Protocol::PutPair(pair) => Protocol::PutPairResult(self.table.put_pair(&pair)),
+                    // puts are upserts so identical pairs are only stored once
+                    Protocol::PutPair(pair) => Protocol::PutPairResult(self.upsert_pair(&pair)),
+
+                    Protocol::PutPairBatch(pairs) => Protocol::PutPairBatchResult(
+                        pairs.iter().map(|p| self.upsert_pair(p)).collect(),
+                    ),
 
                     Protocol::GetPair(hash) => Protocol::GetPairResult(self.table.pair(&hash)),
 
+                    Protocol::GetPairBatch(hashes) => {
+                        Protocol::GetPairBatchResult(self.table.pairs(&hashes))
+                    }
+
+                    Protocol::GetHeader(hash) => {
+                        Protocol::GetHeaderResult(self.table.header(&hash))
+                    }
+
                     Protocol::ModifyPair {
                         keys,
                         old_pair,
@@ -145,6 +202,8 @@ impl<HT: HashTable> Actor for HashTableActor<HT> {
                         Protocol::GetMetasForPairResult(self.table.metas_for_pair(&pair))
                     }
 
+                    Protocol::Stats => Protocol::StatsResult(self.table.stats()),
+
                     _ => unreachable!(),
                 },
                 Some(context.myself()),
@@ -158,12 +217,18 @@ pub mod tests {
 
     use super::HashTableActor;
     use actor::Protocol;
+    use error::HolochainError;
     use hash::tests::test_hash;
     use hash_table::{
-        memory::tests::test_table, pair::tests::test_pair, test_util::standard_suite, HashTable,
+        entry::tests::test_entry_b,
+        memory::tests::test_table,
+        pair::{tests::test_pair, Pair},
+        test_util::standard_suite,
+        HashTable,
     };
     use key::Key;
     use riker::actors::*;
+    use serde_json;
     use std::{sync::mpsc, thread};
 
     /// dummy table actor ref
@@ -223,9 +288,54 @@ pub mod tests {
         handle.join().unwrap();
     }
 
+    #[test]
+    /// putting the same pair twice stores it once, a different pair under its key is rejected
+    fn put_pair_upsert() {
+        let mut table_actor = test_table_actor();
+
+        table_actor.put_pair(&test_pair()).unwrap();
+        table_actor.put_pair(&test_pair()).unwrap();
+        table_actor.put_pairs(&[test_pair(), test_pair()]).unwrap();
+        assert_eq!(1, table_actor.stats().unwrap().entry_count);
+
+        let collision = Pair::new_unchecked(test_pair().header(), &test_entry_b());
+        assert_eq!(
+            Err(HolochainError::new(&format!(
+                "hash collision: a different pair is already stored under {}",
+                test_pair().key()
+            ))),
+            table_actor.put_pair(&collision),
+        );
+        assert_eq!(
+            Some(test_pair()),
+            table_actor.pair(&test_pair().key()).unwrap()
+        );
+    }
+
+    #[test]
+    /// re-putting a pair with different entry metadata replaces the stored metadata
+    fn put_pair_metadata() {
+        let mut table_actor = test_table_actor();
+        table_actor.put_pair(&test_pair()).unwrap();
+
+        let metadata = serde_json::Value::String("read".to_string());
+        let annotated = Pair::new_unchecked(
+            test_pair().header(),
+            &test_pair().entry().with_metadata(metadata.clone()),
+        );
+        table_actor.put_pair(&annotated).unwrap();
+        assert_eq!(1, table_actor.stats().unwrap().entry_count);
+        assert_eq!(
+            Some(metadata),
+            table_actor
+                .pair(&test_pair().key())
+                .unwrap()
+                .and_then(|p| p.entry().metadata())
+        );
+    }
+
     #[test]
     fn test_standard_suite() {
         standard_suite(&mut test_table_actor());
     }
-
 }
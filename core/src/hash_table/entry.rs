@@ -1,9 +1,9 @@
 use error::HolochainError;
-use hash;
+use hash::HashAlgorithm;
 use hash_table::sys_entry::EntryType;
+use holochain_dna::zome::entry_types::Sharing;
 use json::{FromJson, ToJson};
 use key::Key;
-use multihash::Hash;
 use serde_json;
 use std::{
     hash::{Hash as StdHash, Hasher},
@@ -19,6 +19,25 @@ pub struct Entry {
     // @TODO do NOT serialize entry_type in Entry as it should only be in Header
     // @see https://github.com/holochain/holochain-rust/issues/80
     entry_type: String,
+
+    /// local annotations (e.g. tags, read state) that are stored with the entry
+    /// not part of the hash, so changing them never changes the entry address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metadata: Option<serde_json::Value>,
+
+    /// how the entry is hashed, set by the chain the entry is pushed to
+    #[serde(default, skip_serializing_if = "HashAlgorithm::is_default")]
+    hash_algorithm: HashAlgorithm,
+
+    /// sharing of the entry type, only public entries are published to the DHT
+    /// not part of the hash, defaults to public like entry types in the DNA
+    #[serde(default, skip_serializing_if = "is_public")]
+    sharing: Sharing,
+}
+
+/// true for the default sharing, which is left out of the JSON
+fn is_public(sharing: &Sharing) -> bool {
+    *sharing == Sharing::Public
 }
 
 impl PartialEq for Entry {
@@ -50,6 +69,35 @@ impl Entry {
         Entry {
             entry_type: entry_type.to_string(),
             content: content.to_string(),
+            metadata: None,
+            hash_algorithm: HashAlgorithm::default(),
+            sharing: Sharing::default(),
+        }
+    }
+
+    /// returns a copy of this entry with the given sharing
+    /// the copy has the same hash as this entry
+    pub fn with_sharing(&self, sharing: Sharing) -> Entry {
+        Entry {
+            sharing,
+            ..self.clone()
+        }
+    }
+
+    /// returns a copy of this entry that hashes with the given algorithm
+    pub fn with_hash_algorithm(&self, hash_algorithm: HashAlgorithm) -> Entry {
+        Entry {
+            hash_algorithm,
+            ..self.clone()
+        }
+    }
+
+    /// returns a copy of this entry carrying the given metadata
+    /// the copy has the same hash as this entry
+    pub fn with_metadata(&self, metadata: serde_json::Value) -> Entry {
+        Entry {
+            metadata: Some(metadata),
+            ..self.clone()
         }
     }
 
@@ -59,9 +107,7 @@ impl Entry {
         // @see https://github.com/holochain/holochain-rust/issues/103
         let string_to_hash = &self.content;
 
-        // @TODO the hashing algo should not be hardcoded
-        // @see https://github.com/holochain/holochain-rust/issues/104
-        hash::str_to_b58_hash(string_to_hash, Hash::SHA2256)
+        self.hash_algorithm.hash_str(string_to_hash)
     }
 
     /// content getter
@@ -74,17 +120,43 @@ impl Entry {
         self.entry_type.clone()
     }
 
+    /// hash_algorithm getter
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.hash_algorithm
+    }
+
+    /// metadata getter
+    pub fn metadata(&self) -> Option<serde_json::Value> {
+        self.metadata.clone()
+    }
+
+    /// sharing getter
+    pub fn sharing(&self) -> Sharing {
+        self.sharing.clone()
+    }
+
     /// returns true iff the entry is valid
     pub fn validate(&self) -> bool {
         // always valid if immutable and new() enforces validity
         true
     }
 
+    /// returns true if the content parses as JSON
+    pub fn is_valid_json(&self) -> bool {
+        serde_json::from_str::<serde_json::Value>(&self.content).is_ok()
+    }
+
     /// returns true if the entry type is a system entry
     pub fn is_sys(&self) -> bool {
         EntryType::from_str(&self.entry_type).unwrap() != EntryType::App
     }
 
+    /// returns true if the entry type is a system entry
+    /// same as is_sys(), named for routing entries to a system table
+    pub fn is_system_type(&self) -> bool {
+        self.is_sys()
+    }
+
     /// returns true if the entry type is an app entry
     pub fn is_app(&self) -> bool {
         EntryType::from_str(&self.entry_type).unwrap() == EntryType::App
@@ -115,9 +187,12 @@ impl FromJson for Entry {
 
 #[cfg(test)]
 pub mod tests {
+    use hash::HashAlgorithm;
     use hash_table::{entry::Entry, sys_entry::EntryType};
+    use holochain_dna::zome::entry_types::Sharing;
     use json::{FromJson, ToJson};
     use key::Key;
+    use serde_json;
     use snowflake;
 
     /// dummy entry type
@@ -304,6 +379,24 @@ pub mod tests {
         assert_eq!(test_entry().hash(), test_entry().key());
     }
 
+    #[test]
+    /// tests for entry.with_hash_algorithm()
+    fn with_hash_algorithm() {
+        let e = test_entry().with_hash_algorithm(HashAlgorithm::Sha256Hex);
+
+        assert_eq!(HashAlgorithm::default(), test_entry().hash_algorithm());
+        assert_eq!(HashAlgorithm::Sha256Hex, e.hash_algorithm());
+        assert_eq!(64, e.hash().len());
+        assert_ne!(test_entry().hash(), e.hash());
+
+        // only non-default algorithms are serialized
+        assert!(e.to_json().unwrap().contains("Sha256Hex"));
+        assert_eq!(
+            e.hash(),
+            Entry::from_json(&e.to_json().unwrap()).unwrap().hash()
+        );
+    }
+
     #[test]
     /// test that we can round trip through JSON
     fn json_round_trip() {
@@ -328,10 +421,73 @@ pub mod tests {
         ] {
             let entry = Entry::new(sys_type.as_str(), "");
             assert!(entry.is_sys());
+            assert!(entry.is_system_type());
             assert!(!entry.is_app());
         }
     }
 
+    #[test]
+    /// test that is_system_type() agrees with is_sys()
+    fn is_system_type() {
+        for entry_type in vec![EntryType::Header.as_str(), "%future_type", "foo", "foo%"] {
+            let entry = Entry::new(entry_type, "");
+            assert_eq!(entry.is_sys(), entry.is_system_type());
+        }
+        assert!(!Entry::new("foo", "").is_system_type());
+    }
+
+    #[test]
+    /// tests for entry.with_metadata()
+    fn with_metadata() {
+        let e = test_entry();
+        let metadata: serde_json::Value =
+            serde_json::from_str(r#"{"tags":["foo"],"read":true}"#).unwrap();
+        let annotated = e.with_metadata(metadata.clone());
+
+        assert_eq!(None, e.metadata());
+        assert_eq!(Some(metadata), annotated.metadata());
+        assert_eq!(e.hash(), annotated.hash());
+        assert_eq!(e, annotated);
+
+        // metadata survives a JSON round trip but is left out when there is none
+        assert_eq!(
+            annotated.metadata(),
+            Entry::from_json(&annotated.to_json().unwrap())
+                .unwrap()
+                .metadata()
+        );
+        assert!(!e.to_json().unwrap().contains("metadata"));
+    }
+
+    #[test]
+    /// tests for entry.with_sharing()
+    fn with_sharing() {
+        let e = test_entry();
+        let private = e.with_sharing(Sharing::Private);
+
+        assert_eq!(Sharing::Public, e.sharing());
+        assert_eq!(Sharing::Private, private.sharing());
+        assert_eq!(e.hash(), private.hash());
+
+        // sharing survives a JSON round trip but is left out when public
+        assert_eq!(
+            Sharing::Private,
+            Entry::from_json(&private.to_json().unwrap())
+                .unwrap()
+                .sharing()
+        );
+        assert!(!e.to_json().unwrap().contains("sharing"));
+    }
+
+    #[test]
+    /// test that we can detect JSON content
+    fn is_valid_json() {
+        assert!(Entry::new("foo", r#"{"bar":"baz"}"#).is_valid_json());
+        assert!(Entry::new("foo", "1").is_valid_json());
+        assert!(!Entry::new("foo", "{bar").is_valid_json());
+        assert!(!Entry::new("foo", "").is_valid_json());
+    }
+
     #[test]
     /// test that we can detect app entry types
     fn is_app() {
@@ -1,10 +1,11 @@
+use chain::header::Header;
 use error::HolochainError;
 use std::{
     fs,
     path::{Path, MAIN_SEPARATOR},
 };
 
-use hash_table::{pair::Pair, pair_meta::PairMeta, HashTable};
+use hash_table::{pair::Pair, pair_meta::PairMeta, HashTable, TableStats};
 use json::{FromJson, ToJson};
 use key::Key;
 use std::fs::create_dir_all;
@@ -14,18 +15,22 @@ use walkdir::WalkDir;
 #[derive(Debug, Clone)]
 enum Table {
     Pairs,
+    /// copy of the header of every Pair, so headers can be read without the entries
+    Headers,
     Metas,
 }
 
 // things that can be serialized and put in a file... wish-it-was-rows
 trait Row: ToJson + Key {}
 impl Row for Pair {}
+impl Row for Header {}
 impl Row for PairMeta {}
 
 impl ToString for Table {
     fn to_string(&self) -> String {
         match self {
             Table::Pairs => "pairs",
+            Table::Headers => "headers",
             Table::Metas => "metas",
         }.to_string()
     }
@@ -94,7 +99,8 @@ impl FileTable {
 
 impl HashTable for FileTable {
     fn put_pair(&mut self, pair: &Pair) -> Result<(), HolochainError> {
-        self.upsert(Table::Pairs, pair)
+        self.upsert(Table::Pairs, pair)?;
+        self.upsert(Table::Headers, pair.header())
     }
 
     fn pair(&self, key: &str) -> Result<Option<Pair>, HolochainError> {
@@ -104,6 +110,15 @@ impl HashTable for FileTable {
         }
     }
 
+    /// reads the header file only, so the entry is never read
+    /// Pairs put before headers had their own files fall back to reading the whole Pair
+    fn header(&self, key: &str) -> Result<Option<Header>, HolochainError> {
+        match self.lookup(Table::Headers, key)? {
+            Some(json) => Ok(Some(Header::from_json(&json)?)),
+            None => Ok(self.pair(key)?.map(|pair| pair.header().clone())),
+        }
+    }
+
     fn assert_pair_meta(&mut self, meta: &PairMeta) -> Result<(), HolochainError> {
         self.upsert(Table::Metas, meta)
     }
@@ -139,6 +154,26 @@ impl HashTable for FileTable {
         metas.sort();
         Ok(metas)
     }
+
+    fn stats(&self) -> Result<TableStats, HolochainError> {
+        let mut stats = TableStats::default();
+
+        // brute force, every pair file is read and parsed
+        for pair in WalkDir::new(self.dir(Table::Pairs)?) {
+            let pair = pair?;
+            let path = pair.path();
+            if let Some(stem) = path.file_stem() {
+                if let Some(key) = stem.to_str() {
+                    if let Some(pair) = self.pair(&key)? {
+                        stats.entry_count += 1;
+                        stats.total_bytes += pair.entry().content().len();
+                    }
+                }
+            }
+        }
+
+        Ok(stats)
+    }
 }
 
 #[cfg(test)]
@@ -147,7 +182,9 @@ pub mod tests {
     use error::HolochainError;
     use hash_table::{
         file::{FileTable, Row},
+        pair::tests::test_pair_unique,
         test_util::standard_suite,
+        HashTable,
     };
     use json::ToJson;
     use key::Key;
@@ -196,7 +233,11 @@ pub mod tests {
             Regex::new(&regex_str).expect("failed to build regex")
         };
 
-        for (s, t) in vec![("pairs", Table::Pairs), ("metas", Table::Metas)] {
+        for (s, t) in vec![
+            ("pairs", Table::Pairs),
+            ("headers", Table::Headers),
+            ("metas", Table::Metas),
+        ] {
             assert!(
                 re(s).is_match(
                     &table
@@ -227,7 +268,11 @@ pub mod tests {
             Regex::new(&regex_str).expect("failed to build regex")
         };
 
-        for (s, t) in vec![("pairs", Table::Pairs), ("metas", Table::Metas)] {
+        for (s, t) in vec![
+            ("pairs", Table::Pairs),
+            ("headers", Table::Headers),
+            ("metas", Table::Metas),
+        ] {
             for k in vec!["foo", "bar"] {
                 assert!(
                     re(s, k).is_match(
@@ -281,4 +326,29 @@ pub mod tests {
         );
     }
 
+    #[test]
+    /// headers are read from their own files without the entries
+    /// pairs stored without a header file still give their header
+    fn test_header_files() {
+        let (mut table, _dir) = test_table();
+
+        let pair = test_pair_unique();
+        table.put_pair(&pair).expect("could not put pair");
+        assert_eq!(
+            Some(pair.header().to_json().expect("could not serialize header")),
+            table
+                .lookup(Table::Headers, &pair.key())
+                .expect("could not lookup header"),
+        );
+
+        let old_pair = test_pair_unique();
+        table
+            .upsert(Table::Pairs, &old_pair)
+            .expect("could not upsert pair");
+        assert_eq!(
+            Ok(Some(old_pair.header().clone())),
+            table.header(&old_pair.key())
+        );
+    }
+
 }
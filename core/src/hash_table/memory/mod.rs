@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
+use chain::header::Header;
 use error::HolochainError;
-use hash_table::{pair::Pair, pair_meta::PairMeta, HashTable};
+use hash_table::{pair::Pair, pair_meta::PairMeta, HashTable, TableStats};
 use key::Key;
 
 /// Struct implementing the HashTable Trait by storing the HashTable in memory
@@ -30,6 +31,10 @@ impl HashTable for MemTable {
         Ok(self.pairs.get(key).cloned())
     }
 
+    fn header(&self, key: &str) -> Result<Option<Header>, HolochainError> {
+        Ok(self.pairs.get(key).map(|pair| pair.header().clone()))
+    }
+
     fn assert_pair_meta(&mut self, meta: &PairMeta) -> Result<(), HolochainError> {
         self.meta.insert(meta.key(), meta.clone());
         Ok(())
@@ -51,6 +56,13 @@ impl HashTable for MemTable {
         metas.sort();
         Ok(metas)
     }
+
+    fn stats(&self) -> Result<TableStats, HolochainError> {
+        Ok(TableStats {
+            entry_count: self.pairs.len(),
+            total_bytes: self.pairs.values().map(|p| p.entry().content().len()).sum(),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -10,6 +10,7 @@ pub mod sys_entry;
 pub mod test_util;
 
 use agent::keys::Keys;
+use chain::header::Header;
 use error::HolochainError;
 use hash_table::{
     pair::Pair,
@@ -20,6 +21,15 @@ use key::Key;
 
 pub type HashString = String;
 
+/// summary of what a HashTable is storing
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct TableStats {
+    /// number of Pairs in the table
+    pub entry_count: usize,
+    /// summed byte length of the content of every Pair's entry
+    pub total_bytes: usize,
+}
+
 /// Trait of the data structure storing the source chain
 /// source chain is stored as a hash table of Pairs.
 /// Pair is a pair holding an Entry and its Header
@@ -38,9 +48,29 @@ pub trait HashTable: Send + Sync + Clone + 'static {
     /// add a Pair to the HashTable, analogous to chain.push() but ordering is not enforced
     fn put_pair(&mut self, pair: &Pair) -> Result<(), HolochainError>;
 
+    /// add many Pairs to the HashTable, e.g. for bulk loading a chain
+    /// stops at the first failure, the Pairs before it stay in the HashTable
+    fn put_pairs(&mut self, pairs: &[Pair]) -> Result<(), HolochainError> {
+        for pair in pairs {
+            self.put_pair(pair)?;
+        }
+        Ok(())
+    }
+
     /// lookup a Pair from the HashTable by Pair/Header key
     fn pair(&self, key: &str) -> Result<Option<Pair>, HolochainError>;
 
+    /// lookup many Pairs from the HashTable by Pair/Header key
+    /// results are positional, i.e. the nth result is the Pair option for the nth key
+    fn pairs(&self, keys: &[String]) -> Result<Vec<Option<Pair>>, HolochainError> {
+        keys.iter().map(|k| self.pair(k)).collect()
+    }
+
+    /// lookup the Header of a Pair from the HashTable by Pair/Header key
+    fn header(&self, key: &str) -> Result<Option<Header>, HolochainError> {
+        Ok(self.pair(key)?.map(|pair| pair.header().clone()))
+    }
+
     /// add a new Pair to the HashTable as per commit and status link an old Pair as MODIFIED
     fn modify_pair(
         &mut self,
@@ -83,6 +113,10 @@ pub trait HashTable: Send + Sync + Clone + 'static {
     /// lookup all PairMeta for a given Pair
     fn metas_for_pair(&mut self, pair: &Pair) -> Result<Vec<PairMeta>, HolochainError>;
 
+    // stats
+    /// count the Pairs in the HashTable and the bytes of their entry content
+    fn stats(&self) -> Result<TableStats, HolochainError>;
+
     // query
     // @TODO how should we handle queries?
     // @see https://github.com/holochain/holochain-rust/issues/141
@@ -1,6 +1,7 @@
-use chain::{header::Header, Chain};
+use chain::header::Header;
 use error::HolochainError;
-use hash_table::entry::Entry;
+use hash_table::{entry::Entry, sys_entry::ToEntry};
+use holochain_dna::zome::entry_types::Sharing;
 use json::{FromJson, RoundTripJson, ToJson};
 use key::Key;
 use serde_json;
@@ -15,9 +16,9 @@ pub struct Pair {
 }
 
 impl Pair {
-    /// build a new Pair from a chain and entry
+    /// build a new Pair from a header and entry
     ///
-    /// Header is generated automatically
+    /// a header for the next Pair of a chain is built with Header::new(chain, entry)
     ///
     /// a Pair is immutable, but the chain is mutable if chain.push() is used.
     ///
@@ -26,26 +27,28 @@ impl Pair {
     /// need to include X. Pair Y can be regenerated with the same parameters as Y' and will be
     /// now be valid, the new Y' will include correct headers pointing to X.
     ///
-    /// # Panics
-    ///
-    /// Panics if entry is somehow invalid
+    /// returns an error if the header does not match the entry
     ///
     /// @see chain::entry::Entry
     /// @see chain::header::Header
-    pub fn new(chain: &Chain, entry: &Entry) -> Pair {
-        let header = Header::new(chain, entry);
+    pub fn new(header: &Header, entry: &Entry) -> Result<Pair, HolochainError> {
+        let p = Pair::new_unchecked(header, entry);
+        if p.validate() {
+            Ok(p)
+        } else {
+            Err(HolochainError::new(
+                "attempted to create a pair with a header that doesn't match its entry",
+            ))
+        }
+    }
 
-        let p = Pair {
-            header: header,
+    /// build a new Pair from a header and entry without validating them
+    /// only for code that knows the header was built for the entry, e.g. tests
+    pub fn new_unchecked(header: &Header, entry: &Entry) -> Pair {
+        Pair {
+            header: header.clone(),
             entry: entry.clone(),
-        };
-
-        // we panic as no code path should attempt to create invalid pairs
-        // creating a Pair is an internal process of chain.push() and is deterministic based on
-        // an immutable Entry (that itself cannot be invalid), so this should never happen.
-        assert!(p.validate(), "attempted to create an invalid pair");
-
-        p
+        }
     }
 
     /// header getter
@@ -58,6 +61,44 @@ impl Pair {
         &self.entry
     }
 
+    /// address of the entry content, i.e. the entry hash
+    /// NOT unique across pairs, pairs with the same entry content share an entry address
+    /// @see https://github.com/holochain/holochain-rust/issues/145
+    pub fn entry_address(&self) -> String {
+        self.entry.hash()
+    }
+
+    /// address of the pair itself, i.e. the header hash used as the pair key
+    pub fn header_address(&self) -> String {
+        self.key()
+    }
+
+    /// true if the entry is public, i.e. the pair may be published to the DHT
+    pub fn is_public(&self) -> bool {
+        self.entry.sharing() == Sharing::Public
+    }
+
+    /// a copy of this pair with an empty entry of the same type in place of the entry
+    /// for passing header data around without cloning entry content
+    /// the copy doesn't validate as its header doesn't match the placeholder entry
+    pub fn header_only(&self) -> Pair {
+        Pair::new_unchecked(&self.header, &Entry::new(self.header.entry_type(), ""))
+    }
+
+    /// the links of the header as a JSON object, null for absent links
+    /// e.g. {"link":"Qm...","link_same_type":null}
+    pub fn links_json(&self) -> String {
+        serde_json::to_string(&PairLinks {
+            link: self.header.link(),
+            link_same_type: self.header.link_same_type(),
+        }).expect("links should always serialize")
+    }
+
+    /// the header as a system entry and the content entry, e.g. for putting both in a table
+    pub fn to_entry_pair(&self) -> (Entry, Entry) {
+        (self.header.to_entry(), self.entry.clone())
+    }
+
     /// true if the pair is valid
     pub fn validate(&self) -> bool {
         // the header and entry must validate independently
@@ -69,6 +110,13 @@ impl Pair {
     }
 }
 
+/// the links of a Pair's header, for links_json()
+#[derive(Serialize)]
+struct PairLinks {
+    link: Option<String>,
+    link_same_type: Option<String>,
+}
+
 impl Key for Pair {
     fn key(&self) -> String {
         self.header.hash()
@@ -105,16 +153,28 @@ impl RoundTripJson for Pair {}
 #[cfg(test)]
 pub mod tests {
     use super::Pair;
-    use chain::{header::Header, tests::test_chain, SourceChain};
-    use hash_table::entry::{
-        tests::{test_entry, test_entry_b, test_entry_unique},
-        Entry,
+    use chain::{header::Header, tests::test_chain, Chain, SourceChain};
+    use error::HolochainError;
+    use hash_table::{
+        entry::{
+            tests::{test_entry, test_entry_b, test_entry_unique},
+            Entry,
+        },
+        sys_entry::{EntryType, ToEntry},
     };
+    use holochain_dna::zome::entry_types::Sharing;
     use json::{FromJson, ToJson};
+    use key::Key;
+    use serde_json;
+
+    /// dummy pair for the given chain and entry
+    pub fn test_pair_for(chain: &Chain, entry: &Entry) -> Pair {
+        Pair::new_unchecked(&Header::new(chain, entry), entry)
+    }
 
     /// dummy pair
     pub fn test_pair() -> Pair {
-        Pair::new(&test_chain(), &test_entry())
+        test_pair_for(&test_chain(), &test_entry())
     }
 
     /// dummy pair, same as test_pair()
@@ -124,12 +184,25 @@ pub mod tests {
 
     /// dummy pair, differs from test_pair()
     pub fn test_pair_b() -> Pair {
-        Pair::new(&test_chain(), &test_entry_b())
+        test_pair_for(&test_chain(), &test_entry_b())
     }
 
     /// dummy pair, uses test_entry_unique()
     pub fn test_pair_unique() -> Pair {
-        Pair::new(&test_chain(), &test_entry_unique())
+        test_pair_for(&test_chain(), &test_entry_unique())
+    }
+
+    /// builds the next pair for the chain with the given header timestamp
+    /// @TODO use Header::new() once headers carry real timestamps
+    /// @see https://github.com/holochain/holochain-rust/issues/70
+    pub fn test_pair_with_timestamp(chain: &Chain, entry: &Entry, timestamp: &str) -> Pair {
+        let mut json: serde_json::Value = serde_json::from_str(
+            &test_pair_for(chain, entry)
+                .to_json()
+                .expect("test pair should serialize"),
+        ).expect("test pair json should parse");
+        json["header"]["timestamp"] = serde_json::Value::String(timestamp.to_string());
+        Pair::from_json(&json.to_string()).expect("test pair json should deserialize")
     }
 
     #[test]
@@ -143,9 +216,18 @@ pub mod tests {
         assert_eq!(h1.entry_hash(), e1.hash());
         assert_eq!(h1.link(), None);
 
-        let p1 = Pair::new(&chain, &e1.clone());
+        let p1 = Pair::new(&h1, &e1.clone()).expect("a header built for an entry should match it");
         assert_eq!(&e1, p1.entry());
         assert_eq!(&h1, p1.header());
+
+        // a header built for another entry doesn't match
+        let e2 = Entry::new(t, "other content");
+        assert_eq!(
+            Err(HolochainError::new(
+                "attempted to create a pair with a header that doesn't match its entry",
+            )),
+            Pair::new(&h1, &e2),
+        );
     }
 
     #[test]
@@ -156,7 +238,7 @@ pub mod tests {
         let c = "bar";
         let e = Entry::new(t, c);
         let h = Header::new(&chain, &e);
-        let p = Pair::new(&chain, &e);
+        let p = test_pair_for(&chain, &e);
 
         assert_eq!(&h, p.header());
     }
@@ -181,11 +263,93 @@ pub mod tests {
         let t = "fooType";
 
         let e1 = Entry::new(t, "bar");
-        let p1 = Pair::new(&chain, &e1);
+        let p1 = test_pair_for(&chain, &e1);
 
         assert!(p1.validate());
     }
 
+    #[test]
+    /// tests for pair.entry_address() and pair.header_address()
+    fn addresses() {
+        let mut chain = test_chain();
+        let e = test_entry();
+
+        let p1 = chain
+            .push_entry(&e)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+        let p2 = chain
+            .push_entry(&e)
+            .expect("pushing a valid entry to an exlusively owned chain shouldn't fail");
+
+        assert_eq!(e.hash(), p1.entry_address());
+        assert_eq!(p1.entry_address(), p2.entry_address());
+
+        assert_eq!(p1.key(), p1.header_address());
+        assert_ne!(p1.header_address(), p2.header_address());
+    }
+
+    #[test]
+    /// tests for pair.is_public()
+    fn is_public() {
+        let chain = test_chain();
+        let e = test_entry();
+
+        assert!(test_pair_for(&chain, &e).is_public());
+        assert!(!test_pair_for(&chain, &e.with_sharing(Sharing::Private)).is_public());
+        assert!(!test_pair_for(&chain, &e.with_sharing(Sharing::Encrypted)).is_public());
+    }
+
+    #[test]
+    /// tests for pair.header_only()
+    fn header_only() {
+        let pair = test_pair();
+        let header_only = pair.header_only();
+
+        assert_eq!(pair.header(), header_only.header());
+        assert_eq!(pair.key(), header_only.key());
+        assert_eq!("", header_only.entry().content());
+        assert_eq!(pair.entry().entry_type(), header_only.entry().entry_type());
+        assert!(!header_only.validate());
+    }
+
+    #[test]
+    /// tests for pair.links_json()
+    fn links_json() {
+        let mut chain = test_chain();
+        let mut pairs = Vec::new();
+        for e in vec![test_entry(), test_entry_b(), test_entry()] {
+            pairs.push(
+                chain
+                    .push_entry(&e)
+                    .expect("pushing a valid entry to an exlusively owned chain shouldn't fail"),
+            );
+        }
+
+        assert_eq!(
+            format!(
+                "{{\"link\":\"{}\",\"link_same_type\":\"{}\"}}",
+                pairs[1].key(),
+                pairs[0].key()
+            ),
+            pairs[2].links_json(),
+        );
+        assert_eq!(
+            "{\"link\":null,\"link_same_type\":null}",
+            pairs[0].links_json()
+        );
+    }
+
+    #[test]
+    /// tests for pair.to_entry_pair()
+    fn to_entry_pair() {
+        let pair = test_pair();
+        let (header_entry, entry) = pair.to_entry_pair();
+
+        assert_eq!(EntryType::Header.as_str(), header_entry.entry_type());
+        assert_eq!(pair.header(), &Header::new_from_entry(&header_entry));
+        assert_eq!(pair.entry(), &entry);
+    }
+
     #[test]
     /// test JSON roundtrip for pairs
     fn json_roundtrip() {
@@ -1,3 +1,4 @@
+use chain::header::Header;
 use hash_table::entry::Entry;
 use holochain_agent::{Agent, Identity};
 use holochain_dna::Dna;
@@ -99,6 +100,24 @@ impl ToEntry for Agent {
     }
 }
 
+//-------------------------------------------------------------------------------------------------
+// Header Entry
+//-------------------------------------------------------------------------------------------------
+
+impl ToEntry for Header {
+    fn to_entry(&self) -> Entry {
+        Entry::new(
+            EntryType::Header.as_str(),
+            &serde_json::to_string(&self).expect("Header should serialize"),
+        )
+    }
+
+    fn new_from_entry(entry: &Entry) -> Self {
+        assert!(EntryType::from_str(&entry.entry_type()).unwrap() == EntryType::Header);
+        serde_json::from_str(&entry.content()).expect("entry is not a valid Header Entry")
+    }
+}
+
 //-------------------------------------------------------------------------------------------------
 // UNIT TESTS
 //-------------------------------------------------------------------------------------------------
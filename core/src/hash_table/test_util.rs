@@ -9,7 +9,7 @@ use hash_table::{
         PairMeta,
     },
     status::{CrudStatus, LINK_NAME, STATUS_NAME},
-    HashTable,
+    HashTable, TableStats,
 };
 use key::Key;
 
@@ -23,6 +23,71 @@ pub fn test_pair_round_trip<HT: HashTable>(table: &mut HT) {
     assert_eq!(table.pair(&pair.key()), Ok(Some(pair)));
 }
 
+pub fn test_pairs_batch<HT: HashTable>(table: &mut HT) {
+    let pair_1 = test_pair_unique();
+    let pair_2 = test_pair_unique();
+    let missing = test_pair_unique();
+
+    table
+        .put_pair(&pair_1)
+        .expect("should be able to commit valid pair");
+    table
+        .put_pair(&pair_2)
+        .expect("should be able to commit valid pair");
+
+    assert_eq!(
+        Ok(vec![Some(pair_1.clone()), None, Some(pair_2.clone())]),
+        table.pairs(&[pair_1.key(), missing.key(), pair_2.key()]),
+    );
+}
+
+pub fn test_header<HT: HashTable>(table: &mut HT) {
+    let pair = test_pair_unique();
+    let missing = test_pair_unique();
+
+    table
+        .put_pair(&pair)
+        .expect("should be able to commit valid pair");
+
+    assert_eq!(Ok(Some(pair.header().clone())), table.header(&pair.key()));
+    assert_eq!(Ok(None), table.header(&missing.key()));
+}
+
+pub fn test_put_pairs<HT: HashTable>(table: &mut HT) {
+    let pairs = vec![test_pair_unique(), test_pair_unique(), test_pair_unique()];
+
+    table
+        .put_pairs(&pairs)
+        .expect("should be able to commit valid pairs");
+
+    for pair in pairs {
+        assert_eq!(table.pair(&pair.key()), Ok(Some(pair)));
+    }
+}
+
+pub fn test_stats<HT: HashTable>(table: &mut HT) {
+    let before = table.stats().expect("should be able to get table stats");
+
+    let pair_1 = test_pair_unique();
+    let pair_2 = test_pair_unique();
+    table
+        .put_pair(&pair_1)
+        .expect("should be able to commit valid pair");
+    table
+        .put_pair(&pair_2)
+        .expect("should be able to commit valid pair");
+
+    assert_eq!(
+        Ok(TableStats {
+            entry_count: before.entry_count + 2,
+            total_bytes: before.total_bytes
+                + pair_1.entry().content().len()
+                + pair_2.entry().content().len(),
+        }),
+        table.stats(),
+    );
+}
+
 pub fn test_modify_pair<HT: HashTable>(table: &mut HT) {
     let pair_1 = test_pair_unique();
     let pair_2 = test_pair_unique();
@@ -150,6 +215,14 @@ pub fn standard_suite<HT: HashTable>(table: &mut HT) {
 
     test_pair_round_trip(table);
 
+    test_pairs_batch(table);
+
+    test_header(table);
+
+    test_put_pairs(table);
+
+    test_stats(table);
+
     test_modify_pair(table);
 
     test_retract_pair(table);
@@ -1,9 +1,10 @@
-//use error::HolochainError;
-use action::ActionWrapper;
+use action::{Action, ActionWrapper};
 use context::Context;
+use error::HolochainError;
 use state::State;
 use std::{
     sync::{
+        atomic::{AtomicUsize, Ordering},
         mpsc::{channel, Receiver, Sender},
         Arc, RwLock, RwLockReadGuard,
     },
@@ -19,13 +20,91 @@ pub struct Instance {
     state: Arc<RwLock<State>>,
     action_channel: Sender<ActionWrapper>,
     observer_channel: Sender<Observer>,
+    /// cap on the observers registered by dispatch_with_observer() and zome API functions
+    observer_limit: ObserverLimit,
 }
 
 type ClosureType = Box<FnMut(&State) -> bool + Send>;
 
+/// returns true for the actions an Observer should be run after
+pub type ActionFilter = fn(&Action) -> bool;
+
 /// State Observer that executes a closure everytime the State changes.
 pub struct Observer {
     pub sensor: ClosureType,
+    /// only run the sensor after actions accepted by the filter, None runs it after every action
+    /// a Heartbeat always runs every sensor
+    pub action_filter: Option<ActionFilter>,
+    /// room taken under an ObserverLimit, given back when the observer is dropped once done
+    reservation: Option<ObserverReservation>,
+}
+
+impl Observer {
+    /// true if the sensor should run now that the given action has been reduced
+    fn observes(&self, action: &Action) -> bool {
+        match (action, self.action_filter) {
+            (Action::Heartbeat, _) | (_, None) => true,
+            (_, Some(action_filter)) => action_filter(action),
+        }
+    }
+}
+
+/// cap on the number of observers registered with an action loop at once
+/// clones share the cap and the count, so the cap can be changed while observers are running
+#[derive(Clone, Debug, Default)]
+pub struct ObserverLimit {
+    /// number of reserved observers that haven't finished yet
+    count: Arc<AtomicUsize>,
+    /// cap on count, None for no cap
+    max: Arc<RwLock<Option<usize>>>,
+}
+
+impl ObserverLimit {
+    /// the cap on reserved observers, None if there is no cap
+    pub fn max(&self) -> Option<usize> {
+        *self
+            .max
+            .read()
+            .expect("owners of the max observers RwLock shouldn't panic")
+    }
+
+    /// set the cap on reserved observers, None removes the cap
+    pub fn set_max(&self, max: Option<usize>) {
+        *self
+            .max
+            .write()
+            .expect("owners of the max observers RwLock shouldn't panic") = max;
+    }
+
+    /// number of reserved observers that haven't finished yet
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::SeqCst)
+    }
+
+    /// takes room for an observer about to be registered
+    /// returns HolochainError::Busy without taking any if the cap is reached
+    fn reserve(&self) -> Result<ObserverReservation, HolochainError> {
+        let count = self.count.fetch_add(1, Ordering::SeqCst);
+        let reservation = ObserverReservation {
+            count: self.count.clone(),
+        };
+        match self.max() {
+            // dropping the reservation gives the room back
+            Some(max) if count >= max => Err(HolochainError::Busy),
+            _ => Ok(reservation),
+        }
+    }
+}
+
+/// room for one observer under an ObserverLimit, given back when dropped
+struct ObserverReservation {
+    count: Arc<AtomicUsize>,
+}
+
+impl Drop for ObserverReservation {
+    fn drop(&mut self) {
+        self.count.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 pub static DISPATCH_WITHOUT_CHANNELS: &str = "dispatch called without channels open";
@@ -61,21 +140,66 @@ impl Instance {
 
     /// Stack an action in the Event Queue and create an Observer on it with the specified closure
     ///
+    /// returns HolochainError::Busy without dispatching anything if max_observers() observers
+    /// are already registered, callers can retry once some of them have finished
+    ///
     /// # Panics
     ///
     /// Panics if called before `start_action_loop`.
-    pub fn dispatch_with_observer<F>(&mut self, action_wrapper: ActionWrapper, closure: F)
+    pub fn dispatch_with_observer<F>(
+        &mut self,
+        action_wrapper: ActionWrapper,
+        closure: F,
+    ) -> Result<(), HolochainError>
     where
         F: 'static + FnMut(&State) -> bool + Send,
     {
-        dispatch_action_with_observer(
+        dispatch_action_with_limited_observer(
             &self.action_channel,
             &self.observer_channel,
+            &self.observer_limit,
             action_wrapper,
             closure,
         )
     }
 
+    /// dispatch_with_observer() with the closure only run after actions the filter accepts
+    /// e.g. to stop an observer waiting on a zome call result polling after every other action
+    pub fn dispatch_with_filtered_observer<F>(
+        &mut self,
+        action_wrapper: ActionWrapper,
+        action_filter: ActionFilter,
+        closure: F,
+    ) -> Result<(), HolochainError>
+    where
+        F: 'static + FnMut(&State) -> bool + Send,
+    {
+        let reservation = self.observer_limit.reserve()?;
+        dispatch_action_with_observer_for(
+            &self.action_channel,
+            &self.observer_channel,
+            action_wrapper,
+            Observer {
+                sensor: Box::new(closure),
+                action_filter: Some(action_filter),
+                reservation: Some(reservation),
+            },
+        );
+        Ok(())
+    }
+
+    /// the cap on concurrently registered observers, None if there is no cap
+    pub fn max_observers(&self) -> Option<usize> {
+        self.observer_limit.max()
+    }
+
+    /// set the cap on concurrently registered observers, None removes the cap
+    /// only observers registered by dispatch_with_observer() and zome API functions count
+    /// towards the cap, the reducers and dispatch_and_wait() are never held back by it
+    pub fn set_max_observers(&mut self, max_observers: Option<usize>) {
+        self.observer_limit.set_max(max_observers);
+    }
+
     /// Returns recievers for actions and observers that get added to this instance
     fn initialize_channels(&mut self) -> (Receiver<ActionWrapper>, Receiver<Observer>) {
         let (tx_action, rx_action) = channel::<ActionWrapper>();
@@ -90,6 +214,12 @@ impl Instance {
     pub fn start_action_loop(&mut self, context: Arc<Context>) {
         let (rx_action, rx_observer) = self.initialize_channels();
 
+        // zome API functions only have the context to find the observer limit of this instance
+        let context = Arc::new(Context {
+            observer_limit: self.observer_limit.clone(),
+            ..(*context).clone()
+        });
+
         let sync_self = self.clone();
 
         thread::spawn(move || {
@@ -114,6 +244,9 @@ impl Instance {
         rx_observer: &Receiver<Observer>,
         context: &Arc<Context>,
     ) -> Vec<Observer> {
+        // kept for deciding which observers to run once the state owns the action wrapper
+        let action = action_wrapper.action().clone();
+
         // Mutate state
         {
             let mut state = self
@@ -132,6 +265,7 @@ impl Instance {
         state_observers.extend(rx_observer.try_iter());
 
         // Run all observer closures
+        // done observers are dropped here, which gives back any room they took under the limit
         {
             let state = self
                 .state
@@ -139,7 +273,8 @@ impl Instance {
                 .expect("owners of the state RwLock shouldn't panic");
             let mut i = 0;
             while i != state_observers.len() {
-                if (&mut state_observers[i].sensor)(&state) {
+                if state_observers[i].observes(&action) && (&mut state_observers[i].sensor)(&state)
+                {
                     state_observers.remove(i);
                 } else {
                     i += 1;
@@ -157,6 +292,7 @@ impl Instance {
             state: Arc::new(RwLock::new(State::new())),
             action_channel: tx_action,
             observer_channel: tx_observer,
+            observer_limit: ObserverLimit::default(),
         }
     }
 
@@ -220,10 +356,82 @@ pub fn dispatch_action_with_observer<F>(
 ) where
     F: 'static + FnMut(&State) -> bool + Send,
 {
-    let observer = Observer {
-        sensor: Box::new(closure),
-    };
+    dispatch_action_with_observer_for(
+        action_channel,
+        observer_channel,
+        action_wrapper,
+        Observer {
+            sensor: Box::new(closure),
+            action_filter: None,
+            reservation: None,
+        },
+    );
+}
+
+/// dispatch_action_with_observer() with the observer counted against the given limit until it
+/// is done, e.g. for zome API functions
+/// returns HolochainError::Busy without dispatching anything if the limit is reached
+///
+/// # Panics
+///
+/// Panics if the channels passed are disconnected.
+pub fn dispatch_action_with_limited_observer<F>(
+    action_channel: &Sender<ActionWrapper>,
+    observer_channel: &Sender<Observer>,
+    observer_limit: &ObserverLimit,
+    action_wrapper: ActionWrapper,
+    closure: F,
+) -> Result<(), HolochainError>
+where
+    F: 'static + FnMut(&State) -> bool + Send,
+{
+    let reservation = observer_limit.reserve()?;
+    dispatch_action_with_observer_for(
+        action_channel,
+        observer_channel,
+        action_wrapper,
+        Observer {
+            sensor: Box::new(closure),
+            action_filter: None,
+            reservation: Some(reservation),
+        },
+    );
+    Ok(())
+}
+
+/// dispatch_action_with_observer() with the closure only run after actions the filter accepts
+///
+/// # Panics
+///
+/// Panics if the channels passed are disconnected.
+pub fn dispatch_action_with_filtered_observer<F>(
+    action_channel: &Sender<ActionWrapper>,
+    observer_channel: &Sender<Observer>,
+    action_wrapper: ActionWrapper,
+    action_filter: ActionFilter,
+    closure: F,
+) where
+    F: 'static + FnMut(&State) -> bool + Send,
+{
+    dispatch_action_with_observer_for(
+        action_channel,
+        observer_channel,
+        action_wrapper,
+        Observer {
+            sensor: Box::new(closure),
+            action_filter: Some(action_filter),
+            reservation: None,
+        },
+    );
+}
 
+/// registers the observer then sends the action so the observer can't miss it
+fn dispatch_action_with_observer_for(
+    action_channel: &Sender<ActionWrapper>,
+    observer_channel: &Sender<Observer>,
+    action_wrapper: ActionWrapper,
+    observer: Observer,
+) {
     observer_channel
         .send(observer)
         .expect(DISPATCH_WITHOUT_CHANNELS);
@@ -244,10 +452,14 @@ pub fn dispatch_action(action_channel: &Sender<ActionWrapper>, action_wrapper: A
 #[cfg(test)]
 pub mod tests {
     extern crate test_utils;
-    use super::Instance;
-    use action::{tests::test_action_wrapper_get, Action, ActionWrapper};
+    use super::{Instance, Observer, ObserverLimit};
+    use action::{
+        tests::{test_action_wrapper_commit, test_action_wrapper_get, test_action_wrapper_rzfr},
+        Action, ActionWrapper,
+    };
     use agent::state::ActionResponse;
     use context::Context;
+    use error::HolochainError;
     use hash_table::sys_entry::EntryType;
     use holochain_agent::Agent;
     use holochain_dna::{zome::Zome, Dna};
@@ -257,7 +469,11 @@ pub mod tests {
     use state::State;
     use std::{
         str::FromStr,
-        sync::{mpsc::channel, Arc, Mutex},
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            mpsc::channel,
+            Arc, Mutex,
+        },
         thread::sleep,
         time::Duration,
     };
@@ -287,6 +503,10 @@ pub mod tests {
                 agent,
                 logger: logger.clone(),
                 persister: Arc::new(Mutex::new(SimplePersister::new())),
+                log_actions: false,
+                network: None,
+                max_result_size: None,
+                observer_limit: ObserverLimit::default(),
             }),
             logger,
         )
@@ -447,7 +667,7 @@ pub mod tests {
             .get(&aw)
             .expect("action and reponse should be added after Get action dispatch");
 
-        assert_eq!(response, &ActionResponse::GetEntry(None));
+        assert_eq!(response, &ActionResponse::GetEntry(Ok(None)));
     }
 
     #[test]
@@ -466,26 +686,176 @@ pub mod tests {
 
         let dna = Dna::new();
         let (sender, receiver) = channel();
-        instance.dispatch_with_observer(
-            ActionWrapper::new(Action::InitApplication(dna.clone())),
-            move |state: &State| match state.nucleus().dna() {
-                Some(dna) => {
-                    sender
-                        .send(dna)
-                        // the channel stays connected until the first message has been sent
-                        // if this fails that means that it was called after having returned done=true
-                        .expect("observer called after done");
-                    true
-                }
-                None => false,
-            },
-        );
+        instance
+            .dispatch_with_observer(
+                ActionWrapper::new(Action::InitApplication(dna.clone())),
+                move |state: &State| match state.nucleus().dna() {
+                    Some(dna) => {
+                        sender
+                            .send(dna)
+                            // the channel stays connected until the first message has been sent
+                            // if this fails that means that it was called after having returned done=true
+                            .expect("observer called after done");
+                        true
+                    }
+                    None => false,
+                },
+            )
+            .expect("no cap on observers is set");
 
         let stored_dna = receiver.recv().expect("observer dropped before done");
 
         assert_eq!(dna, stored_dna);
     }
 
+    #[test]
+    /// tests that a heartbeat re-runs observers waiting on something outside of the state
+    fn heartbeat_runs_observers() {
+        let mut instance = Instance::new();
+        instance.start_action_loop(test_context("jane"));
+
+        let ready = Arc::new(AtomicBool::new(false));
+        let observer_ready = ready.clone();
+        let (sender, receiver) = channel();
+        instance
+            .dispatch_with_observer(
+                ActionWrapper::new(Action::Heartbeat),
+                move |_state: &State| {
+                    if observer_ready.load(Ordering::SeqCst) {
+                        sender
+                            .send(())
+                            // the channel stays connected until the first message has been sent
+                            // if this fails that means that it was called after having returned done=true
+                            .expect("observer called after done");
+                        true
+                    } else {
+                        false
+                    }
+                },
+            )
+            .expect("no cap on observers is set");
+
+        instance.dispatch_and_wait(ActionWrapper::new(Action::Heartbeat));
+        assert!(receiver.try_recv().is_err());
+
+        ready.store(true, Ordering::SeqCst);
+        instance.dispatch(ActionWrapper::new(Action::Heartbeat));
+        receiver
+            .recv_timeout(Duration::from_millis(1000))
+            .expect("observer should fire on the next heartbeat");
+    }
+
+    #[test]
+    /// tests that filtered observers only run after the actions they are waiting for
+    fn dispatch_with_filtered_observer() {
+        let mut instance = Instance::new();
+        instance.start_action_loop(test_context("jane"));
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let observer_runs = runs.clone();
+        instance
+            .dispatch_with_filtered_observer(
+                test_action_wrapper_commit(),
+                |action| match action {
+                    Action::ReturnZomeFunctionResult(_) => true,
+                    _ => false,
+                },
+                move |_state: &State| {
+                    observer_runs.fetch_add(1, Ordering::SeqCst);
+                    false
+                },
+            )
+            .expect("no cap on observers is set");
+
+        instance.dispatch_and_wait(test_action_wrapper_commit());
+        assert_eq!(0, runs.load(Ordering::SeqCst));
+
+        instance.dispatch_and_wait(test_action_wrapper_rzfr());
+        assert_eq!(1, runs.load(Ordering::SeqCst));
+
+        // heartbeats run every observer
+        instance.dispatch_and_wait(ActionWrapper::new(Action::Heartbeat));
+        assert_eq!(2, runs.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    /// tests that dispatch_with_observer() is rejected once max_observers are registered
+    fn dispatch_with_observer_busy() {
+        let mut instance = Instance::new();
+        instance.start_action_loop(test_context("jane"));
+        instance.set_max_observers(Some(2));
+        assert_eq!(Some(2), instance.max_observers());
+
+        let ready = Arc::new(AtomicBool::new(false));
+        for _ in 0..2 {
+            let observer_ready = ready.clone();
+            instance
+                .dispatch_with_observer(ActionWrapper::new(Action::Heartbeat), move |_| {
+                    observer_ready.load(Ordering::SeqCst)
+                })
+                .expect("observers below the cap should be accepted");
+        }
+
+        assert_eq!(
+            Err(HolochainError::Busy),
+            instance.dispatch_with_observer(ActionWrapper::new(Action::Heartbeat), |_| true),
+        );
+
+        // once the observers are done there is room again
+        ready.store(true, Ordering::SeqCst);
+        instance.dispatch_and_wait(ActionWrapper::new(Action::Heartbeat));
+        instance.dispatch_and_wait(ActionWrapper::new(Action::Heartbeat));
+        assert_eq!(
+            Ok(()),
+            instance.dispatch_with_observer(ActionWrapper::new(Action::Heartbeat), |_| true),
+        );
+    }
+
+    #[test]
+    /// tests that an observer counts against the cap from dispatch until it is done, including
+    /// while it is still waiting in the observer channel
+    fn observer_limit_reservations() {
+        let mut instance = Instance::new();
+        instance.set_max_observers(Some(1));
+        let context = test_context("jane");
+        let (_rx_action, rx_observer) = instance.initialize_channels();
+
+        let done = Arc::new(AtomicBool::new(false));
+        let observer_done = done.clone();
+        instance
+            .dispatch_with_observer(test_action_wrapper_get(), move |_| {
+                observer_done.load(Ordering::SeqCst)
+            })
+            .expect("observers below the cap should be accepted");
+        assert_eq!(1, instance.observer_limit.count());
+
+        // an action processed before the observer is picked up leaves it counted
+        let (_, rx_no_observer) = channel::<Observer>();
+        let observers = instance.process_action(
+            test_action_wrapper_get(),
+            Vec::new(),
+            &rx_no_observer,
+            &context,
+        );
+        assert!(observers.is_empty());
+        assert_eq!(1, instance.observer_limit.count());
+
+        // picked up but not done yet
+        let observers =
+            instance.process_action(test_action_wrapper_get(), observers, &rx_observer, &context);
+        assert_eq!(1, observers.len());
+        assert_eq!(
+            Err(HolochainError::Busy),
+            instance.dispatch_with_observer(test_action_wrapper_get(), |_| true),
+        );
+
+        done.store(true, Ordering::SeqCst);
+        let observers =
+            instance.process_action(test_action_wrapper_get(), observers, &rx_observer, &context);
+        assert!(observers.is_empty());
+        assert_eq!(0, instance.observer_limit.count());
+    }
+
     #[test]
     /// tests that we can dispatch an action and block until it completes
     fn can_dispatch_and_wait() {
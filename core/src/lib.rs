@@ -43,6 +43,7 @@ pub mod instance;
 pub mod json;
 pub mod key;
 pub mod logger;
+pub mod network;
 pub mod nucleus;
 pub mod persister;
 pub mod state;
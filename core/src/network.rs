@@ -0,0 +1,13 @@
+use error::HolochainError;
+use hash_table::pair::Pair;
+
+/// trait that defines the network functionality that holochain_core requires
+/// @TODO flesh out the network abstraction beyond gets
+/// @see https://github.com/holochain/holochain-rust/issues/167
+pub trait Network: Send + Sync {
+    /// get a Pair by Entry key from the network if any node holds it
+    /// a Pair rather than only the Entry so that network gets answer with the same header and
+    /// entry as local gets do in ActionResponse::GetEntry
+    /// errors are for a network that could not be asked, Ok(None) if no node holds the entry
+    fn get_entry(&self, entry_hash: &str) -> Result<Option<Pair>, HolochainError>;
+}
@@ -74,6 +74,11 @@ impl SinglePageManager {
         Ok(mem_buf)
     }
 
+    /// Returns the bytes used on the stack and the total bytes of the memory page
+    pub fn memory_usage(&self) -> (usize, usize) {
+        (self.stack.top() as usize, 65536)
+    }
+
     /// Read data somewhere in stack
     pub fn read(&self, allocation: SinglePageAllocation) -> Vec<u8> {
         return self
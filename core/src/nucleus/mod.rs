@@ -6,11 +6,13 @@ use context::Context;
 use error::HolochainError;
 
 use action::{Action, ActionWrapper, NucleusReduceFn};
-use instance::{dispatch_action_with_observer, Observer};
+use instance::{dispatch_action_with_filtered_observer, Observer};
 use nucleus::{
     ribosome::callback::{genesis::genesis, CallbackParams, CallbackResult},
-    state::{NucleusState, NucleusStatus},
+    state::{CancelFlag, NucleusState, NucleusStatus},
 };
+use serde::de::DeserializeOwned;
+use serde_json;
 use snowflake;
 use std::{
     sync::{
@@ -44,6 +46,21 @@ impl ZomeFnCall {
             parameters: parameters.to_string(),
         }
     }
+
+    /// the (zome, capability, function) that the call resolves to, ignoring parameters
+    /// unlike equality, which also compares parameters and the unique call id
+    pub fn signature(&self) -> (String, String, String) {
+        (
+            self.zome_name.clone(),
+            self.cap_name.clone(),
+            self.fn_name.clone(),
+        )
+    }
+
+    /// deserializes the JSON parameters into the type the zome function expects
+    pub fn parse_parameters<T: DeserializeOwned>(&self) -> Result<T, HolochainError> {
+        Ok(serde_json::from_str(&self.parameters)?)
+    }
 }
 
 /// WIP - Struct for holding data when requesting an Entry Validation (ValidateEntry Action)
@@ -64,25 +81,33 @@ impl EntrySubmission {
     }
 }
 
+/// action filter for observers waiting on a zome call result
+/// every result is returned by a ReturnZomeFunctionResult, cancelling a call included
+fn is_zome_function_result(action: &Action) -> bool {
+    match action {
+        Action::ReturnZomeFunctionResult(_) => true,
+        _ => false,
+    }
+}
+
 /// Dispatch ExecuteZoneFunction to and block until call has finished.
 pub fn call_zome_and_wait_for_result(
     call: ZomeFnCall,
     action_channel: &Sender<ActionWrapper>,
     observer_channel: &Sender<Observer>,
-) -> Result<String, HolochainError> {
+) -> Result<ZomeFnOutput, HolochainError> {
     let call_action_wrapper = ActionWrapper::new(Action::ExecuteZomeFunction(call.clone()));
 
     // Dispatch action with observer closure that waits for a result in the state
     let (sender, receiver) = channel();
-    dispatch_action_with_observer(
+    dispatch_action_with_filtered_observer(
         action_channel,
         observer_channel,
         call_action_wrapper,
+        is_zome_function_result,
         move |state: &super::state::State| {
             if let Some(result) = state.nucleus().zome_call_result(&call) {
-                sender
-                    .send(result.clone())
-                    .expect("local channel to be open");
+                sender.send(result).expect("local channel to be open");
                 true
             } else {
                 false
@@ -98,26 +123,52 @@ pub fn call_zome_and_wait_for_result(
 pub fn call_and_wait_for_result(
     call: ZomeFnCall,
     instance: &mut super::instance::Instance,
-) -> Result<String, HolochainError> {
+) -> Result<ZomeFnOutput, HolochainError> {
     let call_action = ActionWrapper::new(Action::ExecuteZomeFunction(call.clone()));
 
     // Dispatch action with observer closure that waits for a result in the state
     let (sender, receiver) = channel();
-    instance.dispatch_with_observer(call_action, move |state: &super::state::State| {
-        if let Some(result) = state.nucleus().zome_call_result(&call) {
-            sender
-                .send(result.clone())
-                .expect("local channel to be open");
-            true
-        } else {
-            false
-        }
-    });
+    instance.dispatch_with_filtered_observer(
+        call_action,
+        is_zome_function_result,
+        move |state: &super::state::State| {
+            if let Some(result) = state.nucleus().zome_call_result(&call) {
+                sender.send(result).expect("local channel to be open");
+                true
+            } else {
+                false
+            }
+        },
+    )?;
 
     // Block until we got that result through the channel:
     receiver.recv().expect("local channel to work")
 }
 
+/// typed output of a zome function
+/// a guest can type its result by writing {"content_type":"...","payload":"..."} to memory
+/// anything else it writes is treated as an application/json payload
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ZomeFnOutput {
+    pub content_type: String,
+    pub payload: String,
+}
+
+impl ZomeFnOutput {
+    pub fn new(content_type: &str, payload: &str) -> Self {
+        ZomeFnOutput {
+            content_type: content_type.to_string(),
+            payload: payload.to_string(),
+        }
+    }
+
+    /// builds the output from the raw string a zome function wrote to memory
+    pub fn from_raw(raw: &str) -> Self {
+        serde_json::from_str(raw).unwrap_or_else(|_| ZomeFnOutput::new("application/json", raw))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Hash)]
 pub struct ZomeFnResult {
     call: ZomeFnCall,
@@ -280,6 +331,7 @@ fn reduce_execute_zome_function(
         _ => unreachable!(),
     };
     let fc = function_call.clone();
+    let (zome_name, cap_name, _) = fc.signature();
 
     let mut has_error = false;
     let mut result = ZomeFnResult::new(
@@ -288,17 +340,24 @@ fn reduce_execute_zome_function(
     );
 
     if let Some(ref dna) = state.dna {
-        if let Some(ref zome) = dna.get_zome(&fc.zome_name) {
-            if let Some(ref wasm) = dna.get_capability(zome, &fc.cap_name) {
+        if let Some(ref zome) = dna.get_zome(&zome_name) {
+            if let Some(ref wasm) = dna.get_capability(zome, &cap_name) {
                 state.zome_calls.insert(fc.clone(), None);
+                let cancel_flag = CancelFlag::new();
+                state
+                    .zome_call_cancel_flags
+                    .insert(fc.clone(), cancel_flag.clone());
 
                 let action_channel = action_channel.clone();
                 let tx_observer = observer_channel.clone();
                 let code = wasm.code.clone();
                 let app_name = state.dna().unwrap().name;
                 thread::spawn(move || {
+                    if cancel_flag.is_cancelled() {
+                        return;
+                    }
                     let result: ZomeFnResult;
-                    match ribosome::api::call(
+                    match ribosome::api::call_cancellable(
                         &app_name,
                         context,
                         &action_channel,
@@ -306,6 +365,7 @@ fn reduce_execute_zome_function(
                         code,
                         &function_call,
                         Some(function_call.clone().parameters.into_bytes()),
+                        &cancel_flag,
                     ) {
                         Ok(runtime) => {
                             result = ZomeFnResult::new(
@@ -322,6 +382,11 @@ fn reduce_execute_zome_function(
                         }
                     }
 
+                    // the result of a cancelled call was returned when it was cancelled
+                    if cancel_flag.is_cancelled() {
+                        return;
+                    }
+
                     // Send ReturnResult Action
                     action_channel
                         .send(ActionWrapper::new(Action::ReturnZomeFunctionResult(result)))
@@ -333,7 +398,7 @@ fn reduce_execute_zome_function(
                     fc.clone(),
                     Err(HolochainError::CapabilityNotFound(format!(
                         "Capability '{:?}' not found in Zome '{:?}'",
-                        &fc.cap_name, &fc.zome_name
+                        &cap_name, &zome_name
                     ))),
                 );
             }
@@ -343,7 +408,7 @@ fn reduce_execute_zome_function(
                 fc.clone(),
                 Err(HolochainError::ZomeNotFound(format!(
                     "Zome '{:?}' not found",
-                    &fc.zome_name
+                    &zome_name
                 ))),
             );
         }
@@ -387,6 +452,7 @@ fn reduce_validate_entry(
 
 /// Reduce ReturnZomeFunctionResult Action.
 /// Simply drops function call into ribosome_calls state.
+/// A result already stored for the call (e.g. a cancellation) is kept.
 #[allow(unknown_lints)]
 #[allow(needless_pass_by_value)]
 fn reduce_return_zome_function_result(
@@ -400,15 +466,43 @@ fn reduce_return_zome_function_result(
     let fr = unwrap_to!(action => Action::ReturnZomeFunctionResult);
     // @TODO store the action and result directly
     // @see https://github.com/holochain/holochain-rust/issues/198
+    state.zome_call_cancel_flags.remove(&fr.call());
+    if let Some(Some(_)) = state.zome_calls.get(&fr.call()) {
+        return;
+    }
     state.zome_calls.insert(fr.call(), Some(fr.result()));
 }
 
+/// Reduce CancelZomeFunction Action
+/// Signals the execution of the call to abort and stores the cancellation as its result, then
+/// returns it in a ReturnZomeFunctionResult Action so anything waiting on the call wakes up.
+/// Calls that are unknown or already have a result are left alone.
+#[allow(unknown_lints)]
+#[allow(needless_pass_by_value)]
+fn reduce_cancel_zome_function(
+    _context: Arc<Context>,
+    state: &mut NucleusState,
+    action_wrapper: &ActionWrapper,
+    action_channel: &Sender<ActionWrapper>,
+    _observer_channel: &Sender<Observer>,
+) {
+    let action = action_wrapper.action();
+    let call = unwrap_to!(action => Action::CancelZomeFunction);
+    if state.cancel_call(call) {
+        let result = ZomeFnResult::new(call.clone(), Err(HolochainError::new("cancelled")));
+        action_channel
+            .send(ActionWrapper::new(Action::ReturnZomeFunctionResult(result)))
+            .expect("action channel to be open in reducer");
+    }
+}
+
 fn resolve_reducer(action_wrapper: &ActionWrapper) -> Option<NucleusReduceFn> {
     match action_wrapper.action() {
         Action::ReturnInitializationResult(_) => Some(reduce_return_initialization_result),
         Action::InitApplication(_) => Some(reduce_init_application),
         Action::ExecuteZomeFunction(_) => Some(reduce_execute_zome_function),
         Action::ReturnZomeFunctionResult(_) => Some(reduce_return_zome_function_result),
+        Action::CancelZomeFunction(_) => Some(reduce_cancel_zome_function),
         Action::ValidateEntry(_) => Some(reduce_validate_entry),
         _ => None,
     }
@@ -451,7 +545,10 @@ pub mod tests {
         Instance,
     };
     use nucleus::state::tests::test_nucleus_state;
-    use std::sync::{mpsc::channel, Arc};
+    use std::{
+        sync::{mpsc::channel, Arc},
+        time::Duration,
+    };
 
     /// dummy zome name compatible with ZomeFnCall
     pub fn test_zome() -> String {
@@ -498,6 +595,50 @@ pub mod tests {
         assert_ne!(zc1, zc2);
     }
 
+    #[test]
+    /// test that calls differing only by parameters share a signature
+    fn test_zome_call_signature() {
+        let zc1 = ZomeFnCall::new(&test_zome(), &test_capability(), &test_function(), "1");
+        let zc2 = ZomeFnCall::new(&test_zome(), &test_capability(), &test_function(), "2");
+
+        assert_eq!(
+            (test_zome(), test_capability(), test_function()),
+            zc1.signature()
+        );
+        assert_eq!(zc1.signature(), zc2.signature());
+        assert_ne!(zc1, zc2);
+
+        let other = ZomeFnCall::new(&test_zome(), &test_capability(), "other_fn", "1");
+        assert_ne!(zc1.signature(), other.signature());
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct TestParameters {
+        foo: String,
+        bar: u32,
+    }
+
+    #[test]
+    /// test deserializing the parameters of a function call
+    fn test_zome_call_parse_parameters() {
+        let zome_call = ZomeFnCall::new(
+            &test_zome(),
+            &test_capability(),
+            &test_function(),
+            r#"{"foo":"baz","bar":1}"#,
+        );
+        assert_eq!(
+            Ok(TestParameters {
+                foo: "baz".to_string(),
+                bar: 1,
+            }),
+            zome_call.parse_parameters(),
+        );
+
+        let malformed = ZomeFnCall::new(&test_zome(), &test_capability(), &test_function(), "{foo");
+        assert!(malformed.parse_parameters::<TestParameters>().is_err());
+    }
+
     #[test]
     /// test access to function result's function call
     fn test_zome_call_result() {
@@ -513,6 +654,36 @@ pub mod tests {
         assert_eq!(test_call_result().result(), Ok("foo".to_string()));
     }
 
+    #[test]
+    /// test that a typed zome function result keeps its content type
+    fn test_zome_call_result_content_type() {
+        let mut state = NucleusState::new();
+        let typed = test_zome_call();
+        let untyped = test_zome_call();
+
+        state.zome_calls.insert(
+            typed.clone(),
+            Some(Ok(
+                r#"{"content_type":"text/plain","payload":"hello"}"#.to_string()
+            )),
+        );
+        state
+            .zome_calls
+            .insert(untyped.clone(), Some(Ok(r#"{"foo":"bar"}"#.to_string())));
+
+        assert_eq!(
+            Some(Ok(ZomeFnOutput::new("text/plain", "hello"))),
+            state.zome_call_result(&typed),
+        );
+        assert_eq!(
+            Some(Ok(ZomeFnOutput::new(
+                "application/json",
+                r#"{"foo":"bar"}"#
+            ))),
+            state.zome_call_result(&untyped),
+        );
+    }
+
     #[test]
     /// smoke test the init of a nucleus
     fn can_instantiate_nucleus_state() {
@@ -547,6 +718,137 @@ pub mod tests {
         assert!(state.zome_calls.contains_key(&fr.call()));
     }
 
+    /// wat for a zome function "main" that calls hc_commit_entry in an endless loop
+    /// called with parameters that aren't commit args, e.g. "{}", every commit fails right away
+    /// without side effects, so the function only ends when its call is cancelled
+    pub fn test_endless_wat() -> String {
+        r#"
+(module
+    (import "env" "hc_commit_entry"
+        (func $commit
+            (param i32)
+            (result i32)
+        )
+    )
+
+    (memory 1)
+    (export "memory" (memory 0))
+
+    (func
+        (export "main")
+            (param $allocation i32)
+            (result i32)
+
+        (loop
+            (drop
+                (call $commit (get_local $allocation))
+            )
+            (br 0)
+        )
+        (get_local $allocation)
+    )
+)
+"#
+        .to_string()
+    }
+
+    #[test]
+    /// test cancelling a zome call that is stuck in its zome function
+    fn test_cancel_call() {
+        let dna = test_utils::create_test_dna_with_wat(
+            &test_zome(),
+            &test_capability(),
+            Some(&test_endless_wat()),
+        );
+        let mut state = NucleusState::new();
+        state.dna = Some(dna);
+        let call = ZomeFnCall::new(&test_zome(), &test_capability(), "main", "{}");
+        let (sender, receiver) = channel::<ActionWrapper>();
+        let (tx_observer, _observer) = channel::<Observer>();
+
+        reduce_execute_zome_function(
+            test_context("jimmy"),
+            &mut state,
+            &ActionWrapper::new(Action::ExecuteZomeFunction(call.clone())),
+            &sender,
+            &tx_observer,
+        );
+        // the zome function never returns by itself
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+        assert_eq!(None, state.zome_call_result(&call));
+
+        reduce_cancel_zome_function(
+            test_context("jimmy"),
+            &mut state,
+            &ActionWrapper::new(Action::CancelZomeFunction(call.clone())),
+            &sender,
+            &tx_observer,
+        );
+        let cancelled = Err(HolochainError::new("cancelled"));
+        assert_eq!(Some(cancelled.clone()), state.zome_call_result(&call));
+
+        // the cancellation is returned as the result of the call
+        let action_wrapper = receiver
+            .recv_timeout(Duration::from_millis(1000))
+            .expect("cancelling a call should return its result");
+        {
+            let action = action_wrapper.action();
+            let fr = unwrap_to!(action => Action::ReturnZomeFunctionResult);
+            assert_eq!(call, fr.call());
+            assert_eq!(Err(HolochainError::new("cancelled")), fr.result());
+        }
+        reduce_return_zome_function_result(
+            test_context("jimmy"),
+            &mut state,
+            &action_wrapper,
+            &sender,
+            &tx_observer,
+        );
+        assert_eq!(Some(cancelled.clone()), state.zome_call_result(&call));
+
+        // the aborted execution returns nothing else
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+        // only in-flight calls can be cancelled
+        assert!(!state.cancel_call(&call));
+        assert!(!state.cancel_call(&test_zome_call()));
+    }
+
+    #[test]
+    /// test listing the zome calls still in flight
+    fn test_pending_calls() {
+        let dna = test_utils::create_test_dna_with_wat(&test_zome(), &test_capability(), None);
+        let mut state = NucleusState::new();
+        state.dna = Some(dna);
+        let call = test_zome_call();
+        let (sender, receiver) = channel::<ActionWrapper>();
+        let (tx_observer, _observer) = channel::<Observer>();
+
+        assert!(state.pending_calls().is_empty());
+
+        reduce_execute_zome_function(
+            test_context("jimmy"),
+            &mut state,
+            &ActionWrapper::new(Action::ExecuteZomeFunction(call.clone())),
+            &sender,
+            &tx_observer,
+        );
+        assert_eq!(vec![call.clone()], state.pending_calls());
+
+        let action_wrapper = receiver
+            .recv_timeout(Duration::from_millis(1000))
+            .expect("zome call should return a result");
+        reduce_return_zome_function_result(
+            test_context("jimmy"),
+            &mut state,
+            &action_wrapper,
+            &sender,
+            &tx_observer,
+        );
+        assert!(state.zome_call_result(&call).is_some());
+        assert!(state.pending_calls().is_empty());
+    }
+
     #[test]
     /// smoke test the init of a nucleus reduction
     fn can_reduce_initialize_action() {
@@ -674,11 +976,75 @@ pub mod tests {
         let result = super::call_and_wait_for_result(zome_call, &mut instance);
         match result {
             // Result 1337 from WASM (as string)
-            Ok(val) => assert_eq!(val, "1337"),
+            Ok(val) => assert_eq!(val, ZomeFnOutput::new("application/json", "1337")),
             Err(err) => assert_eq!(err, HolochainError::InstanceActive),
         }
     }
 
+    #[test]
+    /// tests that the content type a zome function gives its result reaches the caller
+    fn call_zome_function_content_type() {
+        let wat = r#"
+(module
+    (memory 1)
+    (export "memory" (memory 0))
+    (func (export "main") (param $p0 i32) (result i32)
+        i32.const 47
+    )
+    (data (i32.const 0)
+        "{\"content_type\":\"text/plain\",\"payload\":\"hello\"}"
+    )
+)
+"#;
+        let dna = test_utils::create_test_dna_with_wat("test_zome", "test_cap", Some(wat));
+        let mut instance = test_instance(dna);
+
+        let zome_call = ZomeFnCall::new("test_zome", "test_cap", "main", "");
+
+        assert_eq!(
+            Ok(ZomeFnOutput::new("text/plain", "hello")),
+            super::call_and_wait_for_result(zome_call, &mut instance),
+        );
+    }
+
+    #[test]
+    /// tests that cancelling a call wakes up whatever is waiting on its result
+    fn call_and_wait_for_cancelled_result() {
+        let dna = test_utils::create_test_dna_with_wat(
+            &test_zome(),
+            &test_capability(),
+            Some(&test_endless_wat()),
+        );
+        let mut instance = test_instance(dna);
+        let call = ZomeFnCall::new(&test_zome(), &test_capability(), "main", "{}");
+
+        let (sender, receiver) = channel();
+        let mut waiting_instance = instance.clone();
+        let waiting_call = call.clone();
+        thread::spawn(move || {
+            sender
+                .send(super::call_and_wait_for_result(
+                    waiting_call,
+                    &mut waiting_instance,
+                ))
+                .expect("test channel should be open");
+        });
+
+        while !instance.state().nucleus().pending_calls().contains(&call) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        // the waiter is blocked on a zome function that never returns by itself
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+
+        instance.dispatch(ActionWrapper::new(Action::CancelZomeFunction(call)));
+        assert_eq!(
+            Err(HolochainError::new("cancelled")),
+            receiver
+                .recv_timeout(Duration::from_millis(1000))
+                .expect("cancelling a call should wake up the waiter"),
+        );
+    }
+
     #[test]
     /// tests that calling an invalid DNA returns the correct error
     fn call_ribosome_wrong_dna() {
@@ -57,9 +57,11 @@ pub fn invoke_commit_entry(
     let action_wrapper = ActionWrapper::new(Action::Commit(entry));
     // Send Action and block for result
     let (sender, receiver) = channel();
-    ::instance::dispatch_action_with_observer(
+    // the observer blocks this thread until it is done, so the instance caps how many there are
+    let dispatched = ::instance::dispatch_action_with_limited_observer(
         &runtime.action_channel,
         &runtime.observer_channel,
+        &runtime.context.observer_limit,
         action_wrapper.clone(),
         move |state: &::state::State| {
             let mut actions_copy = state.agent().actions();
@@ -79,6 +81,9 @@ pub fn invoke_commit_entry(
             }
         },
     );
+    if dispatched.is_err() {
+        return Ok(Some(RuntimeValue::I32(HcApiReturnCode::ErrorBusy as i32)));
+    }
     // TODO #97 - Return error if timeout or something failed
     // return Err(_);
 
@@ -104,14 +109,16 @@ pub mod tests {
     extern crate test_utils;
     extern crate wabt;
 
-    use super::CommitArgs;
+    use super::{invoke_commit_entry, CommitArgs};
     use hash_table::entry::tests::test_entry;
+    use holochain_wasm_utils::HcApiReturnCode;
     use key::Key;
     use nucleus::ribosome::{
         api::{tests::test_zome_api_function_runtime, ZomeApiFunction},
         Defn,
     };
     use serde_json;
+    use wasmi::{RuntimeArgs, RuntimeValue};
 
     /// dummy commit args from standard test entry
     pub fn test_commit_args_bytes() -> Vec<u8> {
@@ -139,4 +146,33 @@ pub mod tests {
         );
     }
 
+    #[test]
+    /// test that commits are rejected with the busy code while the observer limit is reached
+    fn test_commit_busy() {
+        let (mut runtime, _) = test_zome_api_function_runtime(
+            ZomeApiFunction::CommitAppEntry.as_str(),
+            test_commit_args_bytes(),
+        );
+        let encoded_allocation = runtime
+            .memory_manager
+            .write(&test_commit_args_bytes())
+            .expect("commit args should fit in wasm memory")
+            .encode();
+        let args = [RuntimeValue::I32(encoded_allocation as i32)];
+
+        runtime.context.observer_limit.set_max(Some(0));
+        assert_eq!(
+            Some(RuntimeValue::I32(HcApiReturnCode::ErrorBusy as i32)),
+            invoke_commit_entry(&mut runtime, &RuntimeArgs::from(&args[..]))
+                .expect("a busy commit should not trap"),
+        );
+
+        runtime.context.observer_limit.set_max(None);
+        assert_ne!(
+            Some(RuntimeValue::I32(HcApiReturnCode::ErrorBusy as i32)),
+            invoke_commit_entry(&mut runtime, &RuntimeArgs::from(&args[..]))
+                .expect("committing should not trap"),
+        );
+    }
+
 }
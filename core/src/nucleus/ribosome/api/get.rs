@@ -29,9 +29,11 @@ pub fn invoke_get_entry(
     let action_wrapper = ActionWrapper::new(Action::GetEntry(input.key));
 
     let (sender, receiver) = channel();
-    ::instance::dispatch_action_with_observer(
+    // the observer blocks this thread until it is done, so the instance caps how many there are
+    let dispatched = ::instance::dispatch_action_with_limited_observer(
         &runtime.action_channel,
         &runtime.observer_channel,
+        &runtime.context.observer_limit,
         action_wrapper.clone(),
         move |state: &::state::State| {
             let mut actions_copy = state.agent().actions();
@@ -51,15 +53,18 @@ pub fn invoke_get_entry(
             }
         },
     );
+    if dispatched.is_err() {
+        return Ok(Some(RuntimeValue::I32(HcApiReturnCode::ErrorBusy as i32)));
+    }
     // TODO #97 - Return error if timeout or something failed
     // return Err(_);
 
     let action_result = receiver.recv().expect("observer dropped before done");
 
     match action_result {
-        ActionResponse::GetEntry(maybe_pair) => {
+        ActionResponse::GetEntry(_) => {
             // serialize, allocate and encode result
-            match maybe_pair.to_json() {
+            match action_result.to_json() {
                 Ok(json) => runtime.store_utf8(&json),
                 Err(_) => Ok(Some(RuntimeValue::I32(HcApiReturnCode::ErrorJson as i32))),
             }
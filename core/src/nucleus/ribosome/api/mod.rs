@@ -5,6 +5,7 @@ pub mod init_globals;
 
 use action::ActionWrapper;
 use context::Context;
+use error::HolochainError;
 use holochain_dna::zome::capabilities::ReservedCapabilityNames;
 use holochain_wasm_utils::{HcApiReturnCode, SinglePageAllocation};
 use instance::Observer;
@@ -17,6 +18,7 @@ use nucleus::{
         },
         Defn,
     },
+    state::CancelFlag,
     ZomeFnCall,
 };
 use num_traits::FromPrimitive;
@@ -25,7 +27,7 @@ use std::{
     sync::{mpsc::Sender, Arc},
 };
 use wasmi::{
-    self, Error as InterpreterError, Externals, FuncInstance, FuncRef, ImportsBuilder,
+    self, Error as InterpreterError, Externals, FuncInstance, FuncRef, HostError, ImportsBuilder,
     ModuleImportResolver, ModuleInstance, RuntimeArgs, RuntimeValue, Signature, Trap, TrapKind,
     ValueType,
 };
@@ -140,8 +142,13 @@ pub struct Runtime {
     memory_manager: SinglePageManager,
     zome_call: ZomeFnCall,
     pub app_name: String,
+    /// checked before every zome API function call so a cancelled call stops at the next one
+    cancel_flag: CancelFlag,
 }
 
+/// lets zome API functions abort the wasm execution with a HolochainError, e.g. a cancellation
+impl HostError for HolochainError {}
+
 impl Runtime {
     /// Load a string stored in wasm memory.
     /// Input RuntimeArgs should only have one input which is the encoded allocation holding
@@ -168,14 +175,36 @@ impl Runtime {
             .unwrap()
     }
 
+    /// Returns the bytes used and the total bytes available in wasm memory
+    pub fn memory_usage(&self) -> (usize, usize) {
+        self.memory_manager.memory_usage()
+    }
+
     /// Store a string in wasm memory.
     /// Input should be a a json string.
     /// Returns a Result suitable to return directly from a zome API function, i.e. an encoded allocation
+    /// Returns the ErrorResultTooLarge code if the string is bigger than the context allows
+    /// Returns the ErrorOutOfMemory code if the string doesn't fit in the remaining memory
     pub fn store_utf8(&mut self, json_str: &str) -> Result<Option<RuntimeValue>, Trap> {
+        if let Some(max_result_size) = self.context.max_result_size {
+            if json_str.len() > max_result_size {
+                return Ok(Some(RuntimeValue::I32(
+                    HcApiReturnCode::ErrorResultTooLarge as i32,
+                )));
+            }
+        }
+
         // write str to runtime memory
         let mut s_bytes: Vec<_> = json_str.to_string().into_bytes();
         s_bytes.push(0); // Add string terminate character (important)
 
+        let (used, total) = self.memory_usage();
+        if used + s_bytes.len() >= total {
+            return Ok(Some(RuntimeValue::I32(
+                HcApiReturnCode::ErrorOutOfMemory as i32,
+            )));
+        }
+
         let allocation_of_result = self.memory_manager.write(&s_bytes);
         if allocation_of_result.is_err() {
             return Err(Trap::new(TrapKind::MemoryAccessOutOfBounds));
@@ -203,6 +232,35 @@ pub fn call(
     wasm: Vec<u8>,
     zome_call: &ZomeFnCall,
     parameters: Option<Vec<u8>>,
+) -> Result<Runtime, InterpreterError> {
+    call_cancellable(
+        app_name,
+        context,
+        action_channel,
+        observer_channel,
+        wasm,
+        zome_call,
+        parameters,
+        &CancelFlag::new(),
+    )
+}
+
+/// call() that traps with a "cancelled" error at the first zome API function called after the
+/// flag is cancelled
+/// wasm that never calls a zome API function can't be interrupted and runs to the end
+///
+/// panics if wasm isn't valid
+#[allow(unknown_lints)]
+#[allow(too_many_arguments)]
+pub fn call_cancellable(
+    app_name: &str,
+    context: Arc<Context>,
+    action_channel: &Sender<ActionWrapper>,
+    observer_channel: &Sender<Observer>,
+    wasm: Vec<u8>,
+    zome_call: &ZomeFnCall,
+    parameters: Option<Vec<u8>>,
+    cancel_flag: &CancelFlag,
 ) -> Result<Runtime, InterpreterError> {
     // Create wasm module from wasm binary
     let module = wasmi::Module::from_buffer(wasm).expect("wasm should be valid");
@@ -219,6 +277,11 @@ pub fn call(
             index: usize,
             args: RuntimeArgs,
         ) -> Result<Option<RuntimeValue>, Trap> {
+            if self.cancel_flag.is_cancelled() {
+                return Err(Trap::new(TrapKind::Host(Box::new(HolochainError::new(
+                    "cancelled",
+                )))));
+            }
             let zf = ZomeApiFunction::from_index(index);
             match zf {
                 ZomeApiFunction::MissingNo => panic!("unknown function index"),
@@ -275,6 +338,7 @@ pub fn call(
         memory_manager: SinglePageManager::new(&wasm_instance),
         zome_call: zome_call.clone(),
         app_name: app_name.to_string(),
+        cancel_flag: cancel_flag.clone(),
     };
 
     // scope for mutable borrow of runtime
@@ -323,18 +387,23 @@ pub mod tests {
     extern crate test_utils;
     use super::ZomeApiFunction;
     use context::Context;
+    use holochain_wasm_utils::HcApiReturnCode;
     use instance::{
         tests::{test_context_and_logger, test_instance, TestLogger},
         Instance,
     };
     use nucleus::{
-        ribosome::api::{call, Runtime},
+        ribosome::{
+            api::{call, Runtime},
+            Defn,
+        },
         ZomeFnCall,
     };
     use std::{
         str::FromStr,
         sync::{Arc, Mutex},
     };
+    use wasmi::RuntimeValue;
 
     use holochain_dna::zome::capabilities::ReservedCapabilityNames;
 
@@ -500,6 +569,59 @@ pub mod tests {
         )
     }
 
+    #[test]
+    /// test that storing a string bigger than the free wasm memory returns an error code
+    fn test_store_utf8_out_of_memory() {
+        let (mut runtime, _) = test_zome_api_function_runtime(
+            ZomeApiFunction::Debug.as_str(),
+            "foo".to_string().into_bytes(),
+        );
+
+        let (used, total) = runtime.memory_usage();
+        assert!(used < total);
+
+        let too_big = "x".repeat(total - used);
+        assert_eq!(
+            Some(RuntimeValue::I32(HcApiReturnCode::ErrorOutOfMemory as i32)),
+            runtime
+                .store_utf8(&too_big)
+                .expect("storing too big a string should not trap"),
+        );
+        assert_eq!((used, total), runtime.memory_usage());
+
+        let fits = "x".repeat(10);
+        assert!(runtime.store_utf8(&fits).is_ok());
+        assert_eq!((used + fits.len() + 1, total), runtime.memory_usage());
+    }
+
+    #[test]
+    /// test that storing a string bigger than the context allows returns an error code
+    fn test_store_utf8_result_too_large() {
+        let (mut runtime, _) = test_zome_api_function_runtime(
+            ZomeApiFunction::Debug.as_str(),
+            "foo".to_string().into_bytes(),
+        );
+        runtime.context = Arc::new(runtime.context.with_max_result_size(10));
+
+        let usage = runtime.memory_usage();
+        assert_eq!(
+            Some(RuntimeValue::I32(
+                HcApiReturnCode::ErrorResultTooLarge as i32
+            )),
+            runtime
+                .store_utf8(&"x".repeat(11))
+                .expect("storing too large a result should not trap"),
+        );
+        assert_eq!(usage, runtime.memory_usage());
+
+        assert_ne!(
+            Some(RuntimeValue::I32(
+                HcApiReturnCode::ErrorResultTooLarge as i32
+            )),
+            runtime.store_utf8(&"x".repeat(10)).unwrap(),
+        );
+    }
+
     #[test]
     /// test the FromStr implementation for ZomeApiFunction
     fn test_from_str() {
@@ -170,7 +170,7 @@ pub fn call(
     // translate the call result to a callback result
     match call_result {
         // empty string OK = Success
-        Ok(ref s) if s.is_empty() => CallbackResult::Pass,
+        Ok(ref output) if output.payload.is_empty() => CallbackResult::Pass,
 
         // things that = NotImplemented
         Err(HolochainError::CapabilityNotFound(_)) => CallbackResult::NotImplemented,
@@ -187,7 +187,7 @@ pub fn call(
         }
 
         // string value or error = fail
-        Ok(s) => CallbackResult::Fail(s),
+        Ok(output) => CallbackResult::Fail(output.payload),
         Err(err) => CallbackResult::Fail(err.to_string()),
     }
 }
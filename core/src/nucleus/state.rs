@@ -1,7 +1,13 @@
 use error::HolochainError;
 use holochain_dna::Dna;
-use nucleus::ZomeFnCall;
-use std::collections::HashMap;
+use nucleus::{ZomeFnCall, ZomeFnOutput};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum NucleusStatus {
@@ -17,6 +23,31 @@ impl Default for NucleusStatus {
     }
 }
 
+/// shared flag a launched zome call execution checks to know it should abort
+#[derive(Clone, Debug, Default)]
+pub struct CancelFlag(Arc<AtomicBool>);
+
+impl CancelFlag {
+    pub fn new() -> Self {
+        CancelFlag::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// flags are equal if they are shared by the same execution
+impl PartialEq for CancelFlag {
+    fn eq(&self, other: &CancelFlag) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct NucleusState {
     pub dna: Option<Dna>,
@@ -26,6 +57,8 @@ pub struct NucleusState {
     // @TODO should this use the standard ActionWrapper/ActionResponse format?
     // @see https://github.com/holochain/holochain-rust/issues/196
     pub zome_calls: HashMap<ZomeFnCall, Option<Result<String, HolochainError>>>,
+    /// cancel flags shared with the executions of zome calls still in flight
+    pub zome_call_cancel_flags: HashMap<ZomeFnCall, CancelFlag>,
 }
 
 impl NucleusState {
@@ -34,17 +67,50 @@ impl NucleusState {
             dna: None,
             status: NucleusStatus::New,
             zome_calls: HashMap::new(),
+            zome_call_cancel_flags: HashMap::new(),
         }
     }
 
+    /// the typed result of a zome call, None while the call is still running
     pub fn zome_call_result(
         &self,
         zome_call: &ZomeFnCall,
-    ) -> Option<Result<String, HolochainError>> {
+    ) -> Option<Result<ZomeFnOutput, HolochainError>> {
         match self.zome_calls.get(zome_call) {
             None => None,
-            Some(value) => value.clone(),
+            Some(value) => value
+                .clone()
+                .map(|result| result.map(|raw| ZomeFnOutput::from_raw(&raw))),
+        }
+    }
+
+    /// the zome calls that are still running, i.e. have no result yet
+    pub fn pending_calls(&self) -> Vec<ZomeFnCall> {
+        self.zome_calls
+            .iter()
+            .filter(|(_, result)| result.is_none())
+            .map(|(zome_call, _)| zome_call.clone())
+            .collect()
+    }
+
+    /// signals the execution of an in-flight zome call to abort and stores a cancellation error
+    /// as its result
+    /// returns false if the call is unknown or already has a result
+    /// running instances cancel calls by reducing Action::CancelZomeFunction, which also returns
+    /// the cancellation to anything waiting on the call
+    pub fn cancel_call(&mut self, zome_call: &ZomeFnCall) -> bool {
+        match self.zome_calls.get(zome_call) {
+            Some(None) => {}
+            _ => return false,
+        }
+        if let Some(flag) = self.zome_call_cancel_flags.remove(zome_call) {
+            flag.cancel();
         }
+        self.zome_calls.insert(
+            zome_call.clone(),
+            Some(Err(HolochainError::new("cancelled"))),
+        );
+        true
     }
 
     pub fn has_initialized(&self) -> bool {
@@ -13,6 +13,7 @@
 //! use holochain_agent::Agent;
 //! use std::sync::{Arc, Mutex};
 //! use holochain_core::context::Context;
+//! use holochain_core::instance::ObserverLimit;
 //! use holochain_core::logger::SimpleLogger;
 //! use holochain_core::persister::SimplePersister;
 //!
@@ -28,6 +29,10 @@
 //!     agent: agent,
 //!     logger: Arc::new(Mutex::new(SimpleLogger {})),
 //!     persister: Arc::new(Mutex::new(SimplePersister::new())),
+//!     log_actions: false,
+//!     network: None,
+//!     max_result_size: None,
+//!     observer_limit: ObserverLimit::default(),
 //! };
 //! let mut hc = Holochain::new(dna,Arc::new(context)).unwrap();
 //!
@@ -60,7 +65,7 @@ use holochain_core::{
     context::Context,
     error::HolochainError,
     instance::Instance,
-    nucleus::{call_and_wait_for_result, state::NucleusStatus, ZomeFnCall},
+    nucleus::{call_and_wait_for_result, state::NucleusStatus, ZomeFnCall, ZomeFnOutput},
     state::State,
 };
 use holochain_dna::Dna;
@@ -98,7 +103,7 @@ impl Holochain {
             } else {
                 false
             }
-        });
+        })?;
 
         // TODO: what is the right timeout?
         // had to increase this number when merging develop into feature branch 221-dna-improvements
@@ -150,7 +155,7 @@ impl Holochain {
         cap: &str,
         fn_name: &str,
         params: &str,
-    ) -> Result<String, HolochainError> {
+    ) -> Result<ZomeFnOutput, HolochainError> {
         if !self.active {
             return Err(HolochainError::InstanceNotActive);
         }
@@ -177,6 +182,7 @@ mod tests {
     use super::*;
     use holochain_core::{
         context::Context,
+        instance::ObserverLimit,
         nucleus::ribosome::{callback::Callback, Defn},
         persister::SimplePersister,
     };
@@ -195,6 +201,10 @@ mod tests {
                 agent: agent,
                 logger: logger.clone(),
                 persister: Arc::new(Mutex::new(SimplePersister::new())),
+                log_actions: false,
+                network: None,
+                max_result_size: None,
+                observer_limit: ObserverLimit::default(),
             }),
             logger,
         )
@@ -350,7 +360,7 @@ mod tests {
         // always returns not implemented error for now!
         let result = hc.call("test_zome", "test_cap", "hello", "");
         match result {
-            Ok(result) => assert_eq!(result, "{\"holo\":\"world\"}"),
+            Ok(result) => assert_eq!(result.payload, "{\"holo\":\"world\"}"),
             Err(_) => assert!(false),
         };
     }
@@ -390,7 +400,7 @@ mod tests {
         );
         match result {
             Ok(result) => assert_eq!(
-                result,
+                result.payload,
                 r#"{"input_int_val_plus2":4,"input_str_val_plus_dog":"fish.puppy"}"#
             ),
             Err(_) => assert!(false),
@@ -420,7 +430,7 @@ mod tests {
         // Expect normal OK result with hash
         match result {
             Ok(result) => assert_eq!(
-                result,
+                result.payload,
                 r#"{"hash":"QmRN6wdp1S2A5EtjW9A3M1vKSBuQQGcgvuhoMUoEz4iiT5"}"#
             ),
             Err(_) => assert!(false),
@@ -454,7 +464,7 @@ mod tests {
 
         // Expect normal OK result with hash
         match result {
-            Ok(result) => assert_eq!(result, r#"{"hash":"fail"}"#),
+            Ok(result) => assert_eq!(result.payload, r#"{"hash":"fail"}"#),
             Err(_) => assert!(false),
         };
 
@@ -484,7 +494,7 @@ mod tests {
 
         // Call the exposed wasm function that calls the Commit API function
         let result = hc.call("test_zome", "test_cap", "debug_hello", r#"{}"#);
-        assert!(result.unwrap().is_empty());
+        assert!(result.unwrap().payload.is_empty());
 
         let test_logger = test_logger.lock().unwrap();
         assert_eq!(
@@ -520,7 +530,7 @@ mod tests {
 
         // TODO #165 - check runtime.print_output instead
         // Expect empty OK result
-        assert!(result.unwrap().is_empty());
+        assert!(result.unwrap().payload.is_empty());
 
         // Check in holochain instance's history that the deb event has been processed
         // @TODO don't use history length in tests
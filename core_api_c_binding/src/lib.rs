@@ -9,7 +9,7 @@ use holochain_dna::Dna;
 use std::sync::Arc;
 
 use holochain_agent::Agent;
-use holochain_core::{logger::Logger, persister::SimplePersister};
+use holochain_core::{instance::ObserverLimit, logger::Logger, persister::SimplePersister};
 use std::{
     ffi::{CStr, CString},
     os::raw::c_char,
@@ -31,6 +31,10 @@ pub unsafe extern "C" fn holochain_new(ptr: *mut Dna) -> *mut Holochain {
         agent,
         logger: Arc::new(Mutex::new(NullLogger {})),
         persister: Arc::new(Mutex::new(SimplePersister::new())),
+        log_actions: false,
+        network: None,
+        max_result_size: None,
+        observer_limit: ObserverLimit::default(),
     });
 
     assert!(!ptr.is_null());
@@ -97,7 +101,7 @@ pub unsafe extern "C" fn holochain_call(
         function.as_str(),
         parameters.as_str(),
     ) {
-        Ok(string_result) => match CString::new(string_result) {
+        Ok(output) => match CString::new(output.payload) {
             Ok(s) => s.into_raw(),
             Err(_) => std::ptr::null_mut(),
         },
@@ -4,7 +4,9 @@ extern crate holochain_core_api;
 extern crate holochain_dna;
 
 use holochain_agent::Agent;
-use holochain_core::{context::Context, logger::SimpleLogger, persister::SimplePersister};
+use holochain_core::{
+    context::Context, instance::ObserverLimit, logger::SimpleLogger, persister::SimplePersister,
+};
 use holochain_core_api::*;
 use holochain_dna::Dna;
 use std::{
@@ -41,6 +43,10 @@ fn main() {
         agent,
         logger: Arc::new(Mutex::new(SimpleLogger {})),
         persister: Arc::new(Mutex::new(SimplePersister::new())),
+        log_actions: false,
+        network: None,
+        max_result_size: None,
+        observer_limit: ObserverLimit::default(),
     };
     let mut hc = Holochain::new(dna, Arc::new(context)).unwrap();
     println!("Created a new instance with identity: {}", identity);
@@ -4,7 +4,9 @@ extern crate holochain_dna;
 extern crate wabt;
 
 use holochain_agent::Agent;
-use holochain_core::{context::Context, logger::Logger, persister::SimplePersister};
+use holochain_core::{
+    context::Context, instance::ObserverLimit, logger::Logger, persister::SimplePersister,
+};
 use holochain_dna::{
     wasm::DnaWasm,
     zome::{capabilities::Capability, Config, Zome},
@@ -109,6 +111,10 @@ pub fn test_context_and_logger(agent_name: &str) -> (Arc<Context>, Arc<Mutex<Tes
             agent,
             logger: logger.clone(),
             persister: Arc::new(Mutex::new(SimplePersister::new())),
+            log_actions: false,
+            network: None,
+            max_result_size: None,
+            observer_limit: ObserverLimit::default(),
         }),
         logger,
     )
@@ -20,6 +20,9 @@ pub enum HcApiReturnCode {
     ErrorPageOverflow = 3 << 16,
     ErrorActionResult = 4 << 16,
     ErrorCallbackResult = 5 << 16,
+    ErrorOutOfMemory = 6 << 16,
+    ErrorResultTooLarge = 7 << 16,
+    ErrorBusy = 8 << 16,
 }
 
 //pub fn decode_error(encoded_allocation: u32) -> HcApiReturnCode {
@@ -34,6 +37,9 @@ pub fn encode_error(offset: u16) -> HcApiReturnCode {
         2 => HcApiReturnCode::ErrorJson,
         3 => HcApiReturnCode::ErrorPageOverflow,
         4 => HcApiReturnCode::ErrorActionResult,
+        6 => HcApiReturnCode::ErrorOutOfMemory,
+        7 => HcApiReturnCode::ErrorResultTooLarge,
+        8 => HcApiReturnCode::ErrorBusy,
         1 | _ => HcApiReturnCode::Error,
     }
 }
@@ -232,6 +238,12 @@ pub mod tests {
         assert_eq!(super::encode_error(3), HcApiReturnCode::ErrorPageOverflow);
 
         assert_eq!(super::encode_error(4), HcApiReturnCode::ErrorActionResult);
+
+        assert_eq!(super::encode_error(6), HcApiReturnCode::ErrorOutOfMemory);
+
+        assert_eq!(super::encode_error(7), HcApiReturnCode::ErrorResultTooLarge);
+
+        assert_eq!(super::encode_error(8), HcApiReturnCode::ErrorBusy);
     }
 
     #[test]
@@ -279,6 +291,24 @@ pub mod tests {
             HcApiReturnCode::ErrorActionResult,
         );
 
+        assert_eq!(
+            // offset 6 = out of memory error
+            SinglePageAllocation::new(0b0000000000000110_0000000000000000).unwrap_err(),
+            HcApiReturnCode::ErrorOutOfMemory,
+        );
+
+        assert_eq!(
+            // offset 7 = result too large error
+            SinglePageAllocation::new(0b0000000000000111_0000000000000000).unwrap_err(),
+            HcApiReturnCode::ErrorResultTooLarge,
+        );
+
+        assert_eq!(
+            // offset 8 = busy error
+            SinglePageAllocation::new(0b0000000000001000_0000000000000000).unwrap_err(),
+            HcApiReturnCode::ErrorBusy,
+        );
+
         assert_eq!(
             // nonsense offset = generic error
             SinglePageAllocation::new(0b1010101010101010_0000000000000000).unwrap_err(),